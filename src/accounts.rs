@@ -0,0 +1,1785 @@
+//! Named accounts of lending instructions
+
+use crate::{error::LendingError, instruction::LendingInstruction};
+use solana_program::{
+    instruction::{AccountMeta, CompiledInstruction, Instruction},
+    message::Message,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use std::slice::Iter;
+
+/// Accounts of an `InitLendingMarket` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct InitLendingMarketAccounts {
+    /// Init lending market authority
+    pub init_lending_market_authority: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Rent sysvar
+    pub rent_sysvar: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+    /// Pyth oracle program id
+    pub oracle_program: Pubkey,
+    /// Larix oracle program id
+    pub larix_oracle_program: Pubkey,
+    /// Larix oracle id
+    pub larix_oracle: Pubkey,
+}
+
+impl InitLendingMarketAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.init_lending_market_authority, true),
+            AccountMeta::new(self.lending_market, false),
+            AccountMeta::new_readonly(self.rent_sysvar, false),
+            AccountMeta::new_readonly(self.token_program, false),
+            AccountMeta::new_readonly(self.oracle_program, false),
+            AccountMeta::new_readonly(self.larix_oracle_program, false),
+            AccountMeta::new_readonly(self.larix_oracle, false),
+        ]
+    }
+}
+
+/// Accounts of a `SetLendingMarketOwner` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetLendingMarketOwnerAccounts {
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Current lending market owner
+    pub lending_market_owner: Pubkey,
+}
+
+impl SetLendingMarketOwnerAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_owner, true),
+        ]
+    }
+}
+
+/// Accounts of an `InitReserve` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct InitReserveAccounts {
+    /// Reserve account
+    pub reserve: Pubkey,
+    /// Reserve liquidity SPL Token mint
+    pub liquidity_mint: Pubkey,
+    /// Reserve liquidity supply SPL Token account
+    pub liquidity_supply: Pubkey,
+    /// Reserve liquidity fee receiver
+    pub liquidity_fee_receiver: Pubkey,
+    /// Pyth product account, any account when the liquidity is a lp
+    pub pyth_product: Pubkey,
+    /// Pyth price account, BridgePool account when the liquidity is a lp
+    pub params_1: Pubkey,
+    /// Larix oracle account, LpPrice account when the liquidity is a lp
+    pub params_2: Pubkey,
+    /// Reserve collateral SPL Token mint
+    pub collateral_mint: Pubkey,
+    /// Reserve collateral token supply
+    pub collateral_supply: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Lending market owner
+    pub lending_market_owner: Pubkey,
+    /// Supply account of un-collateralized LTokens
+    pub un_coll_supply: Pubkey,
+    /// Clock sysvar
+    pub clock_sysvar: Pubkey,
+    /// Rent sysvar
+    pub rent_sysvar: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+}
+
+impl InitReserveAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.reserve, false),
+            AccountMeta::new_readonly(self.liquidity_mint, false),
+            AccountMeta::new(self.liquidity_supply, false),
+            AccountMeta::new(self.liquidity_fee_receiver, false),
+            AccountMeta::new_readonly(self.pyth_product, false),
+            AccountMeta::new_readonly(self.params_1, false),
+            AccountMeta::new_readonly(self.params_2, false),
+            AccountMeta::new(self.collateral_mint, false),
+            AccountMeta::new(self.collateral_supply, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_owner, true),
+            AccountMeta::new(self.un_coll_supply, false),
+            AccountMeta::new_readonly(self.clock_sysvar, false),
+            AccountMeta::new_readonly(self.rent_sysvar, false),
+            AccountMeta::new_readonly(self.token_program, false),
+        ]
+    }
+}
+
+/// Accounts of a `RefreshReserve` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefreshReserveAccounts {
+    /// Reserve account
+    pub reserve: Pubkey,
+    /// Reserve liquidity oracle account
+    pub oracle: Pubkey,
+    /// Larix oracle
+    pub larix_oracle: Pubkey,
+}
+
+impl RefreshReserveAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.reserve, false),
+            AccountMeta::new_readonly(self.oracle, false),
+            AccountMeta::new_readonly(self.larix_oracle, false),
+        ]
+    }
+}
+
+/// Accounts of a `DepositReserveLiquidity` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct DepositReserveLiquidityAccounts {
+    /// Source liquidity token account
+    pub source_liquidity: Pubkey,
+    /// Destination collateral token account
+    pub destination_collateral: Pubkey,
+    /// Reserve account
+    pub reserve: Pubkey,
+    /// Reserve collateral SPL Token mint
+    pub reserve_collateral_mint: Pubkey,
+    /// Reserve liquidity supply SPL Token account
+    pub reserve_liquidity_supply: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// User transfer authority
+    pub user_transfer_authority: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+}
+
+impl DepositReserveLiquidityAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.source_liquidity, false),
+            AccountMeta::new(self.destination_collateral, false),
+            AccountMeta::new(self.reserve, false),
+            AccountMeta::new(self.reserve_collateral_mint, false),
+            AccountMeta::new(self.reserve_liquidity_supply, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.user_transfer_authority, true),
+            AccountMeta::new_readonly(self.token_program, false),
+        ]
+    }
+}
+
+/// Where the liquidity of a `RedeemReserveCollateral` instruction is paid to
+#[derive(Clone, Debug, PartialEq)]
+pub enum RedeemReserveCollateralDestination {
+    /// Destination liquidity token account
+    Liquidity(Pubkey),
+    /// Withdraw the lp through the bridge program
+    Bridge {
+        /// Bridge pool info
+        bridge_pool: Pubkey,
+        /// Bridge program id
+        bridge_program: Pubkey,
+        /// Bridge withdraw lp account
+        bridge_withdraw_lp: Pubkey,
+    },
+}
+
+/// Accounts of a `RedeemReserveCollateral` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct RedeemReserveCollateralAccounts {
+    /// Source collateral token account
+    pub source_collateral: Pubkey,
+    /// Reserve account
+    pub reserve: Pubkey,
+    /// Reserve collateral SPL Token mint
+    pub reserve_collateral_mint: Pubkey,
+    /// Reserve liquidity supply SPL Token account
+    pub reserve_liquidity_supply: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// User transfer authority
+    pub user_transfer_authority: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+    /// Destination of the redeemed liquidity
+    pub destination: RedeemReserveCollateralDestination,
+}
+
+impl RedeemReserveCollateralAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.source_collateral, false),
+            AccountMeta::new(self.reserve, false),
+            AccountMeta::new(self.reserve_collateral_mint, false),
+            AccountMeta::new(self.reserve_liquidity_supply, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.user_transfer_authority, true),
+            AccountMeta::new_readonly(self.token_program, false),
+        ];
+        match self.destination {
+            RedeemReserveCollateralDestination::Liquidity(destination_liquidity) => {
+                accounts.push(AccountMeta::new(destination_liquidity, false));
+            }
+            RedeemReserveCollateralDestination::Bridge {
+                bridge_pool,
+                bridge_program,
+                bridge_withdraw_lp,
+            } => {
+                accounts.push(AccountMeta::new(bridge_pool, false));
+                accounts.push(AccountMeta::new_readonly(bridge_program, false));
+                accounts.push(AccountMeta::new(bridge_withdraw_lp, false));
+            }
+        }
+        accounts
+    }
+}
+
+/// Accounts of an `InitObligation` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct InitObligationAccounts {
+    /// Obligation account
+    pub obligation: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Obligation owner
+    pub obligation_owner: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+}
+
+impl InitObligationAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.obligation, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.obligation_owner, true),
+            AccountMeta::new_readonly(self.token_program, false),
+        ]
+    }
+}
+
+/// Accounts of a `RefreshObligation` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefreshObligationAccounts {
+    /// Obligation account
+    pub obligation: Pubkey,
+    /// Deposit reserves followed by borrow reserves, in order
+    pub reserves: Vec<Pubkey>,
+}
+
+impl RefreshObligationAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.obligation, false),
+        ];
+        accounts.extend(
+            self.reserves
+                .iter()
+                .map(|pubkey| AccountMeta::new_readonly(*pubkey, false)),
+        );
+        accounts
+    }
+}
+
+/// Accounts of a `DepositObligationCollateral` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct DepositObligationCollateralAccounts {
+    /// Source collateral token account
+    pub source_collateral: Pubkey,
+    /// Destination deposit reserve collateral supply SPL Token account
+    pub destination_collateral: Pubkey,
+    /// Deposit reserve account
+    pub deposit_reserve: Pubkey,
+    /// Obligation account
+    pub obligation: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// Obligation owner
+    pub obligation_owner: Pubkey,
+    /// User transfer authority
+    pub user_transfer_authority: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+    /// Reserves of the obligation
+    pub reserves: Vec<Pubkey>,
+}
+
+impl DepositObligationCollateralAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.source_collateral, false),
+            AccountMeta::new(self.destination_collateral, false),
+            AccountMeta::new_readonly(self.deposit_reserve, false),
+            AccountMeta::new(self.obligation, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.obligation_owner, true),
+            AccountMeta::new_readonly(self.user_transfer_authority, true),
+            AccountMeta::new_readonly(self.token_program, false),
+        ];
+        accounts.extend(
+            self.reserves
+                .iter()
+                .map(|pubkey| AccountMeta::new_readonly(*pubkey, false)),
+        );
+        accounts
+    }
+}
+
+/// Accounts of a `WithdrawObligationCollateral` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithdrawObligationCollateralAccounts {
+    /// Source withdraw reserve collateral supply SPL Token account
+    pub source_collateral: Pubkey,
+    /// Destination collateral token account
+    pub destination_collateral: Pubkey,
+    /// Withdraw reserve account
+    pub withdraw_reserve: Pubkey,
+    /// Obligation account
+    pub obligation: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// Obligation owner
+    pub obligation_owner: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+}
+
+impl WithdrawObligationCollateralAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.source_collateral, false),
+            AccountMeta::new(self.destination_collateral, false),
+            AccountMeta::new_readonly(self.withdraw_reserve, false),
+            AccountMeta::new(self.obligation, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.obligation_owner, true),
+            AccountMeta::new_readonly(self.token_program, false),
+        ]
+    }
+}
+
+/// Accounts of a `BorrowObligationLiquidity` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct BorrowObligationLiquidityAccounts {
+    /// Source borrow reserve liquidity supply SPL Token account
+    pub source_liquidity: Pubkey,
+    /// Destination liquidity token account
+    pub destination_liquidity: Pubkey,
+    /// Borrow reserve account
+    pub borrow_reserve: Pubkey,
+    /// Obligation account
+    pub obligation: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// Obligation owner
+    pub obligation_owner: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+    /// Borrow reserve liquidity fee receiver
+    pub borrow_reserve_liquidity_fee_receiver: Pubkey,
+    /// Larix oracle program account
+    pub larix_oracle_program: Pubkey,
+    /// Mine mint account
+    pub mine_mint: Pubkey,
+}
+
+impl BorrowObligationLiquidityAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.source_liquidity, false),
+            AccountMeta::new(self.destination_liquidity, false),
+            AccountMeta::new(self.borrow_reserve, false),
+            AccountMeta::new(self.obligation, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.obligation_owner, true),
+            AccountMeta::new_readonly(self.token_program, false),
+            AccountMeta::new(self.borrow_reserve_liquidity_fee_receiver, false),
+            AccountMeta::new_readonly(self.larix_oracle_program, false),
+            AccountMeta::new_readonly(self.mine_mint, false),
+        ]
+    }
+}
+
+/// Accounts of a `RepayObligationLiquidity` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepayObligationLiquidityAccounts {
+    /// Source liquidity token account
+    pub source_liquidity: Pubkey,
+    /// Destination repay reserve liquidity supply SPL Token account
+    pub destination_liquidity: Pubkey,
+    /// Repay reserve account
+    pub repay_reserve: Pubkey,
+    /// Obligation account
+    pub obligation: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// User transfer authority
+    pub user_transfer_authority: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+}
+
+impl RepayObligationLiquidityAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.source_liquidity, false),
+            AccountMeta::new(self.destination_liquidity, false),
+            AccountMeta::new(self.repay_reserve, false),
+            AccountMeta::new(self.obligation, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.user_transfer_authority, true),
+            AccountMeta::new_readonly(self.token_program, false),
+        ]
+    }
+}
+
+/// Accounts of a `LiquidateObligation` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiquidateObligationAccounts {
+    /// Source liquidity token account
+    pub source_liquidity: Pubkey,
+    /// Destination collateral token account
+    pub destination_collateral: Pubkey,
+    /// Repay reserve account
+    pub repay_reserve: Pubkey,
+    /// Repay reserve liquidity supply SPL Token account
+    pub repay_reserve_liquidity_supply: Pubkey,
+    /// Withdraw reserve account
+    pub withdraw_reserve: Pubkey,
+    /// Withdraw reserve collateral supply SPL Token account
+    pub withdraw_reserve_collateral_supply: Pubkey,
+    /// Obligation account
+    pub obligation: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// User transfer authority
+    pub user_transfer_authority: Pubkey,
+    /// Clock sysvar
+    pub clock_sysvar: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+}
+
+impl LiquidateObligationAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.source_liquidity, false),
+            AccountMeta::new(self.destination_collateral, false),
+            AccountMeta::new(self.repay_reserve, false),
+            AccountMeta::new(self.repay_reserve_liquidity_supply, false),
+            AccountMeta::new_readonly(self.withdraw_reserve, false),
+            AccountMeta::new(self.withdraw_reserve_collateral_supply, false),
+            AccountMeta::new(self.obligation, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.user_transfer_authority, true),
+            AccountMeta::new_readonly(self.clock_sysvar, false),
+            AccountMeta::new_readonly(self.token_program, false),
+        ]
+    }
+}
+
+/// Accounts of a `FlashLoan` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlashLoanAccounts {
+    /// Source reserve liquidity supply SPL Token account
+    pub source_liquidity: Pubkey,
+    /// Destination liquidity token account
+    pub destination_liquidity: Pubkey,
+    /// Reserve account
+    pub reserve: Pubkey,
+    /// Flash loan fee receiver account
+    pub flash_loan_fee_receiver: Pubkey,
+    /// Host fee receiver
+    pub host_fee_receiver: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+    /// Flash loan receiver program id
+    pub flash_loan_receiver_program: Pubkey,
+    /// Flash loan authority
+    pub flash_loan_authority: Pubkey,
+    /// Additional accounts forwarded to the flash loan receiver program
+    pub flash_loan_receiver_accounts: Vec<AccountMeta>,
+}
+
+impl FlashLoanAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.source_liquidity, false),
+            AccountMeta::new(self.destination_liquidity, false),
+            AccountMeta::new(self.reserve, false),
+            AccountMeta::new(self.flash_loan_fee_receiver, false),
+            AccountMeta::new(self.host_fee_receiver, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.token_program, false),
+            AccountMeta::new_readonly(self.flash_loan_receiver_program, false),
+            AccountMeta::new_readonly(self.flash_loan_authority, true),
+        ];
+        accounts.extend(self.flash_loan_receiver_accounts.iter().cloned());
+        accounts
+    }
+}
+
+/// Accounts of a `SetConfig` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetConfigAccounts {
+    /// Accounts in the layout of the deployed program, passed through as is
+    pub accounts: Vec<AccountMeta>,
+}
+
+impl SetConfigAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        self.accounts.clone()
+    }
+}
+
+/// Accounts of an `InitMining` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct InitMiningAccounts {
+    /// Mining account
+    pub mining: Pubkey,
+    /// Mining owner
+    pub mining_owner: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+}
+
+impl InitMiningAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.mining, false),
+            AccountMeta::new_readonly(self.mining_owner, true),
+            AccountMeta::new_readonly(self.lending_market, false),
+        ]
+    }
+}
+
+/// Accounts of a `RefreshMining` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefreshMiningAccounts {
+    /// Mining account
+    pub mining: Pubkey,
+    /// Reserves in mining
+    pub reserves: Vec<Pubkey>,
+}
+
+impl RefreshMiningAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.mining, false),
+        ];
+        accounts.extend(
+            self.reserves
+                .iter()
+                .map(|pubkey| AccountMeta::new_readonly(*pubkey, false)),
+        );
+        accounts
+    }
+}
+
+/// Accounts of a `DepositMining` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct DepositMiningAccounts {
+    /// Source collateral token account
+    pub source_collateral: Pubkey,
+    /// UnColl deposit supply SPL Token account
+    pub un_coll_supply: Pubkey,
+    /// Mining account
+    pub mining: Pubkey,
+    /// Reserve account holding the bonus
+    pub reserve: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Mining owner
+    pub mining_owner: Pubkey,
+    /// User transfer authority
+    pub user_transfer_authority: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+}
+
+impl DepositMiningAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.source_collateral, false),
+            AccountMeta::new(self.un_coll_supply, false),
+            AccountMeta::new(self.mining, false),
+            AccountMeta::new_readonly(self.reserve, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.mining_owner, false),
+            AccountMeta::new_readonly(self.user_transfer_authority, true),
+            AccountMeta::new_readonly(self.token_program, false),
+        ]
+    }
+}
+
+/// Accounts of a `WithdrawMining` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithdrawMiningAccounts {
+    /// Destination collateral token account
+    pub destination_collateral: Pubkey,
+    /// UnColl deposit supply SPL Token account
+    pub un_coll_supply: Pubkey,
+    /// Mining account
+    pub mining: Pubkey,
+    /// Reserve account
+    pub reserve: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// Mining owner
+    pub mining_owner: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+}
+
+impl WithdrawMiningAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.destination_collateral, false),
+            AccountMeta::new(self.un_coll_supply, false),
+            AccountMeta::new(self.mining, false),
+            AccountMeta::new(self.reserve, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.mining_owner, true),
+            AccountMeta::new_readonly(self.token_program, false),
+        ]
+    }
+}
+
+/// Accounts of a `ClaimMiningMine` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimMiningMineAccounts {
+    /// Mining account
+    pub mining: Pubkey,
+    /// Mine supply
+    pub mine_supply: Pubkey,
+    /// Destination account
+    pub destination: Pubkey,
+    /// Mining owner
+    pub mining_owner: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+    /// Reserves in mining
+    pub reserves: Vec<Pubkey>,
+}
+
+impl ClaimMiningMineAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.mining, false),
+            AccountMeta::new(self.mine_supply, false),
+            AccountMeta::new(self.destination, false),
+            AccountMeta::new_readonly(self.mining_owner, true),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.token_program, false),
+        ];
+        accounts.extend(
+            self.reserves
+                .iter()
+                .map(|pubkey| AccountMeta::new_readonly(*pubkey, false)),
+        );
+        accounts
+    }
+}
+
+/// Accounts of a `ClaimObligationMine` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimObligationMineAccounts {
+    /// Obligation account
+    pub obligation: Pubkey,
+    /// Mine supply
+    pub mine_supply: Pubkey,
+    /// Destination account
+    pub destination: Pubkey,
+    /// Obligation owner
+    pub obligation_owner: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+}
+
+impl ClaimObligationMineAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.obligation, false),
+            AccountMeta::new(self.mine_supply, false),
+            AccountMeta::new(self.destination, false),
+            AccountMeta::new_readonly(self.obligation_owner, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.token_program, false),
+        ]
+    }
+}
+
+/// Accounts of a `ClaimOwnerFee` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimOwnerFeeAccounts {
+    /// Reserve liquidity supply account
+    pub reserve_liquidity_supply: Pubkey,
+    /// Destination account receiving the owner fee
+    pub destination: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Lending market owner
+    pub lending_market_owner: Pubkey,
+}
+
+impl ClaimOwnerFeeAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.reserve_liquidity_supply, false),
+            AccountMeta::new(self.destination, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_owner, true),
+        ]
+    }
+}
+
+/// Accounts of a `ReceivePendingOwner` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceivePendingOwnerAccounts {
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Pending owner
+    pub pending_owner: Pubkey,
+}
+
+impl ReceivePendingOwnerAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.lending_market, false),
+            AccountMeta::new_readonly(self.pending_owner, true),
+        ]
+    }
+}
+
+/// Accounts of a `RefreshReserves` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefreshReservesAccounts {
+    /// Reserve accounts, each with its oracle account: larix oracle or pyth price account
+    pub reserves: Vec<(Pubkey, Pubkey)>,
+}
+
+impl RefreshReservesAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        self.reserves
+            .iter()
+            .flat_map(|(reserve, oracle)| {
+                vec![
+                    AccountMeta::new(*reserve, false),
+                    AccountMeta::new_readonly(*oracle, false),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Accounts of a `LiquidateObligation2` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiquidateObligation2Accounts {
+    /// Source liquidity token account
+    pub source_liquidity: Pubkey,
+    /// Destination collateral token account
+    pub destination_collateral: Pubkey,
+    /// Repay reserve account
+    pub repay_reserve: Pubkey,
+    /// Repay reserve liquidity supply SPL Token account
+    pub repay_reserve_liquidity_supply: Pubkey,
+    /// Withdraw reserve account
+    pub withdraw_reserve: Pubkey,
+    /// Withdraw reserve collateral supply SPL Token account
+    pub withdraw_reserve_collateral_supply: Pubkey,
+    /// Obligation account
+    pub obligation: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// User transfer authority
+    pub user_transfer_authority: Pubkey,
+    /// Token program id
+    pub token_program: Pubkey,
+}
+
+impl LiquidateObligation2Accounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.source_liquidity, false),
+            AccountMeta::new(self.destination_collateral, false),
+            AccountMeta::new(self.repay_reserve, false),
+            AccountMeta::new(self.repay_reserve_liquidity_supply, false),
+            AccountMeta::new_readonly(self.withdraw_reserve, false),
+            AccountMeta::new(self.withdraw_reserve_collateral_supply, false),
+            AccountMeta::new(self.obligation, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.user_transfer_authority, true),
+            AccountMeta::new_readonly(self.token_program, false),
+        ]
+    }
+}
+
+/// Where the mine of a `ClaimMine` instruction is paid to
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClaimMineTargetAccounts {
+    /// Transfer the mine to a token account
+    Destination {
+        /// Destination account
+        destination: Pubkey,
+        /// Source account, the lending market mine supply
+        source: Pubkey,
+    },
+    /// Lock the mine through the larix lock program
+    LockProgram {
+        /// Larix lock program
+        lock_program: Pubkey,
+        /// Larix lock pool
+        lock_pool: Pubkey,
+        /// User larix info account
+        user_info: Pubkey,
+    },
+}
+
+/// Accounts of a `ClaimMine` instruction
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimMineAccounts {
+    /// Token program id
+    pub token_program: Pubkey,
+    /// Lending market account
+    pub lending_market: Pubkey,
+    /// Derived lending market authority
+    pub lending_market_authority: Pubkey,
+    /// Owner of the mining and obligation accounts
+    pub owner: Pubkey,
+    /// Mining account
+    pub mining: Pubkey,
+    /// Obligation account, if the owner has one
+    pub obligation: Option<Pubkey>,
+    /// Where the mine is paid to
+    pub target: ClaimMineTargetAccounts,
+}
+
+impl ClaimMineAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.token_program, false),
+            AccountMeta::new_readonly(self.lending_market, false),
+            AccountMeta::new_readonly(self.lending_market_authority, false),
+            AccountMeta::new_readonly(self.owner, true),
+            AccountMeta::new(self.mining, false),
+        ];
+        if let Some(obligation) = self.obligation {
+            accounts.push(AccountMeta::new(obligation, false));
+        }
+        match self.target {
+            ClaimMineTargetAccounts::Destination {
+                destination,
+                source,
+            } => {
+                accounts.push(AccountMeta::new(destination, false));
+                accounts.push(AccountMeta::new(source, false));
+            }
+            ClaimMineTargetAccounts::LockProgram {
+                lock_program,
+                lock_pool,
+                user_info,
+            } => {
+                accounts.push(AccountMeta::new_readonly(lock_program, false));
+                accounts.push(AccountMeta::new(lock_pool, false));
+                accounts.push(AccountMeta::new(user_info, false));
+            }
+        }
+        accounts
+    }
+}
+
+/// Named accounts of a lending instruction
+#[derive(Clone, Debug, PartialEq)]
+pub enum LendingInstructionAccounts {
+    /// Accounts of an `InitLendingMarket` instruction
+    InitLendingMarket(InitLendingMarketAccounts),
+    /// Accounts of a `SetLendingMarketOwner` instruction
+    SetLendingMarketOwner(SetLendingMarketOwnerAccounts),
+    /// Accounts of an `InitReserve` instruction
+    InitReserve(InitReserveAccounts),
+    /// Accounts of a `RefreshReserve` instruction
+    RefreshReserve(RefreshReserveAccounts),
+    /// Accounts of a `DepositReserveLiquidity` instruction
+    DepositReserveLiquidity(DepositReserveLiquidityAccounts),
+    /// Accounts of a `RedeemReserveCollateral` instruction
+    RedeemReserveCollateral(RedeemReserveCollateralAccounts),
+    /// Accounts of an `InitObligation` instruction
+    InitObligation(InitObligationAccounts),
+    /// Accounts of a `RefreshObligation` instruction
+    RefreshObligation(RefreshObligationAccounts),
+    /// Accounts of a `DepositObligationCollateral` instruction
+    DepositObligationCollateral(DepositObligationCollateralAccounts),
+    /// Accounts of a `WithdrawObligationCollateral` instruction
+    WithdrawObligationCollateral(WithdrawObligationCollateralAccounts),
+    /// Accounts of a `BorrowObligationLiquidity` instruction
+    BorrowObligationLiquidity(BorrowObligationLiquidityAccounts),
+    /// Accounts of a `RepayObligationLiquidity` instruction
+    RepayObligationLiquidity(RepayObligationLiquidityAccounts),
+    /// Accounts of a `LiquidateObligation` instruction
+    LiquidateObligation(LiquidateObligationAccounts),
+    /// Accounts of a `FlashLoan` instruction
+    FlashLoan(FlashLoanAccounts),
+    /// Accounts of a `SetConfig` instruction
+    SetConfig(SetConfigAccounts),
+    /// Accounts of an `InitMining` instruction
+    InitMining(InitMiningAccounts),
+    /// Accounts of a `RefreshMining` instruction
+    RefreshMining(RefreshMiningAccounts),
+    /// Accounts of a `DepositMining` instruction
+    DepositMining(DepositMiningAccounts),
+    /// Accounts of a `WithdrawMining` instruction
+    WithdrawMining(WithdrawMiningAccounts),
+    /// Accounts of a `ClaimMiningMine` instruction
+    ClaimMiningMine(ClaimMiningMineAccounts),
+    /// Accounts of a `ClaimObligationMine` instruction
+    ClaimObligationMine(ClaimObligationMineAccounts),
+    /// Accounts of a `ClaimOwnerFee` instruction
+    ClaimOwnerFee(ClaimOwnerFeeAccounts),
+    /// Accounts of a `ReceivePendingOwner` instruction
+    ReceivePendingOwner(ReceivePendingOwnerAccounts),
+    /// Accounts of a `RefreshReserves` instruction
+    RefreshReserves(RefreshReservesAccounts),
+    /// Accounts of a `LiquidateObligation2` instruction
+    LiquidateObligation2(LiquidateObligation2Accounts),
+    /// Accounts of a `ClaimMine` instruction
+    ClaimMine(ClaimMineAccounts),
+}
+
+impl LendingInstructionAccounts {
+    /// Account metas in the order the program expects them
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        match self {
+            LendingInstructionAccounts::InitLendingMarket(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::SetLendingMarketOwner(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::InitReserve(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::RefreshReserve(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::DepositReserveLiquidity(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::RedeemReserveCollateral(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::InitObligation(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::RefreshObligation(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::DepositObligationCollateral(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::WithdrawObligationCollateral(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::BorrowObligationLiquidity(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::RepayObligationLiquidity(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::LiquidateObligation(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::FlashLoan(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::SetConfig(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::InitMining(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::RefreshMining(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::DepositMining(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::WithdrawMining(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::ClaimMiningMine(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::ClaimObligationMine(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::ClaimOwnerFee(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::ReceivePendingOwner(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::RefreshReserves(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::LiquidateObligation2(accounts) => accounts.to_account_metas(),
+            LendingInstructionAccounts::ClaimMine(accounts) => accounts.to_account_metas(),
+        }
+    }
+}
+
+/// A lending instruction together with its named accounts
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedInstruction {
+    /// Instruction data
+    pub instruction: LendingInstruction,
+    /// Instruction accounts
+    pub accounts: LendingInstructionAccounts,
+}
+
+/// Decodes the data and accounts of a lending instruction.
+///
+/// Accounts past the ones an instruction expects are ignored unless the instruction takes a
+/// variable number of accounts. The optional accounts of `RedeemReserveCollateral` and
+/// `ClaimMine` must match one of their layouts exactly.
+pub fn decode(data: &[u8], accounts: &[AccountMeta]) -> Result<DecodedInstruction, ProgramError> {
+    let instruction = LendingInstruction::unpack(data)?;
+    let iter = &mut accounts.iter();
+    let accounts = match instruction {
+        LendingInstruction::InitLendingMarket { .. } => {
+            LendingInstructionAccounts::InitLendingMarket(InitLendingMarketAccounts {
+                init_lending_market_authority: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                rent_sysvar: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+                oracle_program: next_account_key(iter)?,
+                larix_oracle_program: next_account_key(iter)?,
+                larix_oracle: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::SetLendingMarketOwner { .. } => {
+            LendingInstructionAccounts::SetLendingMarketOwner(SetLendingMarketOwnerAccounts {
+                lending_market: next_account_key(iter)?,
+                lending_market_owner: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::InitReserve { .. } => {
+            LendingInstructionAccounts::InitReserve(InitReserveAccounts {
+                reserve: next_account_key(iter)?,
+                liquidity_mint: next_account_key(iter)?,
+                liquidity_supply: next_account_key(iter)?,
+                liquidity_fee_receiver: next_account_key(iter)?,
+                pyth_product: next_account_key(iter)?,
+                params_1: next_account_key(iter)?,
+                params_2: next_account_key(iter)?,
+                collateral_mint: next_account_key(iter)?,
+                collateral_supply: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                lending_market_owner: next_account_key(iter)?,
+                un_coll_supply: next_account_key(iter)?,
+                clock_sysvar: next_account_key(iter)?,
+                rent_sysvar: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::RefreshReserve => {
+            LendingInstructionAccounts::RefreshReserve(RefreshReserveAccounts {
+                reserve: next_account_key(iter)?,
+                oracle: next_account_key(iter)?,
+                larix_oracle: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::DepositReserveLiquidity { .. } => {
+            LendingInstructionAccounts::DepositReserveLiquidity(DepositReserveLiquidityAccounts {
+                source_liquidity: next_account_key(iter)?,
+                destination_collateral: next_account_key(iter)?,
+                reserve: next_account_key(iter)?,
+                reserve_collateral_mint: next_account_key(iter)?,
+                reserve_liquidity_supply: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                lending_market_authority: next_account_key(iter)?,
+                user_transfer_authority: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::RedeemReserveCollateral { .. } => {
+            let source_collateral = next_account_key(iter)?;
+            let reserve = next_account_key(iter)?;
+            let reserve_collateral_mint = next_account_key(iter)?;
+            let reserve_liquidity_supply = next_account_key(iter)?;
+            let lending_market = next_account_key(iter)?;
+            let lending_market_authority = next_account_key(iter)?;
+            let user_transfer_authority = next_account_key(iter)?;
+            let token_program = next_account_key(iter)?;
+            let destination = match iter.len() {
+                0 => {
+                    msg!("Redeem destination account is missing");
+                    return Err(ProgramError::NotEnoughAccountKeys);
+                }
+                1 => RedeemReserveCollateralDestination::Liquidity(next_account_key(iter)?),
+                3 => RedeemReserveCollateralDestination::Bridge {
+                    bridge_pool: next_account_key(iter)?,
+                    bridge_program: next_account_key(iter)?,
+                    bridge_withdraw_lp: next_account_key(iter)?,
+                },
+                count => {
+                    msg!("Redeem destination expects 1 or 3 accounts, got {}", count);
+                    return Err(LendingError::InvalidAccountInput.into());
+                }
+            };
+            LendingInstructionAccounts::RedeemReserveCollateral(RedeemReserveCollateralAccounts {
+                source_collateral,
+                reserve,
+                reserve_collateral_mint,
+                reserve_liquidity_supply,
+                lending_market,
+                lending_market_authority,
+                user_transfer_authority,
+                token_program,
+                destination,
+            })
+        }
+        LendingInstruction::InitObligation => {
+            LendingInstructionAccounts::InitObligation(InitObligationAccounts {
+                obligation: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                obligation_owner: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::RefreshObligation => {
+            LendingInstructionAccounts::RefreshObligation(RefreshObligationAccounts {
+                obligation: next_account_key(iter)?,
+                reserves: remaining_account_keys(iter),
+            })
+        }
+        LendingInstruction::DepositObligationCollateral { .. } => {
+            LendingInstructionAccounts::DepositObligationCollateral(
+                DepositObligationCollateralAccounts {
+                    source_collateral: next_account_key(iter)?,
+                    destination_collateral: next_account_key(iter)?,
+                    deposit_reserve: next_account_key(iter)?,
+                    obligation: next_account_key(iter)?,
+                    lending_market: next_account_key(iter)?,
+                    lending_market_authority: next_account_key(iter)?,
+                    obligation_owner: next_account_key(iter)?,
+                    user_transfer_authority: next_account_key(iter)?,
+                    token_program: next_account_key(iter)?,
+                    reserves: remaining_account_keys(iter),
+                },
+            )
+        }
+        LendingInstruction::WithdrawObligationCollateral { .. } => {
+            LendingInstructionAccounts::WithdrawObligationCollateral(
+                WithdrawObligationCollateralAccounts {
+                    source_collateral: next_account_key(iter)?,
+                    destination_collateral: next_account_key(iter)?,
+                    withdraw_reserve: next_account_key(iter)?,
+                    obligation: next_account_key(iter)?,
+                    lending_market: next_account_key(iter)?,
+                    lending_market_authority: next_account_key(iter)?,
+                    obligation_owner: next_account_key(iter)?,
+                    token_program: next_account_key(iter)?,
+                },
+            )
+        }
+        LendingInstruction::BorrowObligationLiquidity { .. } => {
+            LendingInstructionAccounts::BorrowObligationLiquidity(
+                BorrowObligationLiquidityAccounts {
+                    source_liquidity: next_account_key(iter)?,
+                    destination_liquidity: next_account_key(iter)?,
+                    borrow_reserve: next_account_key(iter)?,
+                    obligation: next_account_key(iter)?,
+                    lending_market: next_account_key(iter)?,
+                    lending_market_authority: next_account_key(iter)?,
+                    obligation_owner: next_account_key(iter)?,
+                    token_program: next_account_key(iter)?,
+                    borrow_reserve_liquidity_fee_receiver: next_account_key(iter)?,
+                    larix_oracle_program: next_account_key(iter)?,
+                    mine_mint: next_account_key(iter)?,
+                },
+            )
+        }
+        LendingInstruction::RepayObligationLiquidity { .. } => {
+            LendingInstructionAccounts::RepayObligationLiquidity(RepayObligationLiquidityAccounts {
+                source_liquidity: next_account_key(iter)?,
+                destination_liquidity: next_account_key(iter)?,
+                repay_reserve: next_account_key(iter)?,
+                obligation: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                user_transfer_authority: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::LiquidateObligation { .. } => {
+            LendingInstructionAccounts::LiquidateObligation(LiquidateObligationAccounts {
+                source_liquidity: next_account_key(iter)?,
+                destination_collateral: next_account_key(iter)?,
+                repay_reserve: next_account_key(iter)?,
+                repay_reserve_liquidity_supply: next_account_key(iter)?,
+                withdraw_reserve: next_account_key(iter)?,
+                withdraw_reserve_collateral_supply: next_account_key(iter)?,
+                obligation: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                lending_market_authority: next_account_key(iter)?,
+                user_transfer_authority: next_account_key(iter)?,
+                clock_sysvar: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::FlashLoan { .. } => {
+            LendingInstructionAccounts::FlashLoan(FlashLoanAccounts {
+                source_liquidity: next_account_key(iter)?,
+                destination_liquidity: next_account_key(iter)?,
+                reserve: next_account_key(iter)?,
+                flash_loan_fee_receiver: next_account_key(iter)?,
+                host_fee_receiver: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                lending_market_authority: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+                flash_loan_receiver_program: next_account_key(iter)?,
+                flash_loan_authority: next_account_key(iter)?,
+                flash_loan_receiver_accounts: iter.cloned().collect(),
+            })
+        }
+        LendingInstruction::SetConfig { .. } => {
+            LendingInstructionAccounts::SetConfig(SetConfigAccounts {
+                accounts: iter.cloned().collect(),
+            })
+        }
+        LendingInstruction::InitMining => {
+            LendingInstructionAccounts::InitMining(InitMiningAccounts {
+                mining: next_account_key(iter)?,
+                mining_owner: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::RefreshMining => {
+            LendingInstructionAccounts::RefreshMining(RefreshMiningAccounts {
+                mining: next_account_key(iter)?,
+                reserves: remaining_account_keys(iter),
+            })
+        }
+        LendingInstruction::DepositMining { .. } => {
+            LendingInstructionAccounts::DepositMining(DepositMiningAccounts {
+                source_collateral: next_account_key(iter)?,
+                un_coll_supply: next_account_key(iter)?,
+                mining: next_account_key(iter)?,
+                reserve: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                mining_owner: next_account_key(iter)?,
+                user_transfer_authority: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::WithdrawMining { .. } => {
+            LendingInstructionAccounts::WithdrawMining(WithdrawMiningAccounts {
+                destination_collateral: next_account_key(iter)?,
+                un_coll_supply: next_account_key(iter)?,
+                mining: next_account_key(iter)?,
+                reserve: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                lending_market_authority: next_account_key(iter)?,
+                mining_owner: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::ClaimMiningMine => {
+            LendingInstructionAccounts::ClaimMiningMine(ClaimMiningMineAccounts {
+                mining: next_account_key(iter)?,
+                mine_supply: next_account_key(iter)?,
+                destination: next_account_key(iter)?,
+                mining_owner: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                lending_market_authority: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+                reserves: remaining_account_keys(iter),
+            })
+        }
+        LendingInstruction::ClaimObligationMine => {
+            LendingInstructionAccounts::ClaimObligationMine(ClaimObligationMineAccounts {
+                obligation: next_account_key(iter)?,
+                mine_supply: next_account_key(iter)?,
+                destination: next_account_key(iter)?,
+                obligation_owner: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                lending_market_authority: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::ClaimOwnerFee => {
+            LendingInstructionAccounts::ClaimOwnerFee(ClaimOwnerFeeAccounts {
+                reserve_liquidity_supply: next_account_key(iter)?,
+                destination: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                lending_market_owner: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::ReceivePendingOwner => {
+            LendingInstructionAccounts::ReceivePendingOwner(ReceivePendingOwnerAccounts {
+                lending_market: next_account_key(iter)?,
+                pending_owner: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::RefreshReserves => {
+            if !iter.len().is_multiple_of(2) {
+                msg!("Each reserve must be followed by its oracle account");
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+            let mut reserves = Vec::with_capacity(iter.len() / 2);
+            while iter.len() > 0 {
+                reserves.push((next_account_key(iter)?, next_account_key(iter)?));
+            }
+            LendingInstructionAccounts::RefreshReserves(RefreshReservesAccounts { reserves })
+        }
+        LendingInstruction::LiquidateObligation2 { .. } => {
+            LendingInstructionAccounts::LiquidateObligation2(LiquidateObligation2Accounts {
+                source_liquidity: next_account_key(iter)?,
+                destination_collateral: next_account_key(iter)?,
+                repay_reserve: next_account_key(iter)?,
+                repay_reserve_liquidity_supply: next_account_key(iter)?,
+                withdraw_reserve: next_account_key(iter)?,
+                withdraw_reserve_collateral_supply: next_account_key(iter)?,
+                obligation: next_account_key(iter)?,
+                lending_market: next_account_key(iter)?,
+                lending_market_authority: next_account_key(iter)?,
+                user_transfer_authority: next_account_key(iter)?,
+                token_program: next_account_key(iter)?,
+            })
+        }
+        LendingInstruction::ClaimMine { .. } => {
+            let token_program = next_account_key(iter)?;
+            let lending_market = next_account_key(iter)?;
+            let lending_market_authority = next_account_key(iter)?;
+            let owner = next_account_key(iter)?;
+            let mining = next_account_key(iter)?;
+            // Three accounts are either the obligation and destination layout or the lock program
+            // layout. The documented flags tell them apart: the obligation is writable, the lock
+            // program is not.
+            let (has_obligation, lock_program) = match iter.len() {
+                0 | 1 => {
+                    msg!("Claim mine target accounts are missing");
+                    return Err(ProgramError::NotEnoughAccountKeys);
+                }
+                2 => (false, false),
+                3 if iter.as_slice()[0].is_writable => (true, false),
+                3 => (false, true),
+                4 => (true, true),
+                count => {
+                    msg!(
+                        "Claim mine expects 2 to 4 accounts after the mining account, got {}",
+                        count
+                    );
+                    return Err(LendingError::InvalidAccountInput.into());
+                }
+            };
+            let obligation = if has_obligation {
+                Some(next_account_key(iter)?)
+            } else {
+                None
+            };
+            let target = if lock_program {
+                ClaimMineTargetAccounts::LockProgram {
+                    lock_program: next_account_key(iter)?,
+                    lock_pool: next_account_key(iter)?,
+                    user_info: next_account_key(iter)?,
+                }
+            } else {
+                ClaimMineTargetAccounts::Destination {
+                    destination: next_account_key(iter)?,
+                    source: next_account_key(iter)?,
+                }
+            };
+            LendingInstructionAccounts::ClaimMine(ClaimMineAccounts {
+                token_program,
+                lending_market,
+                lending_market_authority,
+                owner,
+                mining,
+                obligation,
+                target,
+            })
+        }
+    };
+    Ok(DecodedInstruction {
+        instruction,
+        accounts,
+    })
+}
+
+/// Decodes a lending instruction.
+pub fn decode_instruction(instruction: &Instruction) -> Result<DecodedInstruction, ProgramError> {
+    decode(&instruction.data, &instruction.accounts)
+}
+
+/// Decodes a compiled lending instruction of a transaction message.
+pub fn decode_compiled_instruction(
+    message: &Message,
+    instruction: &CompiledInstruction,
+) -> Result<DecodedInstruction, ProgramError> {
+    let accounts = instruction
+        .accounts
+        .iter()
+        .map(|index| {
+            let index = *index as usize;
+            let pubkey = message
+                .account_keys
+                .get(index)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            Ok(AccountMeta {
+                pubkey: *pubkey,
+                is_signer: message.is_signer(index),
+                is_writable: message.is_writable(index),
+            })
+        })
+        .collect::<Result<Vec<_>, ProgramError>>()?;
+    decode(&instruction.data, &accounts)
+}
+
+fn next_account_key(iter: &mut Iter<AccountMeta>) -> Result<Pubkey, ProgramError> {
+    iter.next()
+        .map(|account| account.pubkey)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+fn remaining_account_keys(iter: &mut Iter<AccountMeta>) -> Vec<Pubkey> {
+    iter.map(|account| account.pubkey).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        instruction::{
+            borrow_obligation_liquidity, claim_mine, claim_obligation_mine, flash_loan,
+            redeem_reserve_collateral, refresh_obligation, refresh_reserves, set_config,
+            ClaimMineTarget,
+        },
+        state::lending_market::LendingMarket,
+    };
+    use solana_program::message::Message;
+
+    fn claim_mine_with(
+        obligation: Option<Pubkey>,
+        target: ClaimMineTarget,
+        keys: &[Pubkey],
+        lending_market: &LendingMarket,
+    ) -> Instruction {
+        claim_mine(
+            crate::id(),
+            100,
+            10_000,
+            keys[0],
+            lending_market,
+            keys[1],
+            keys[2],
+            keys[3],
+            obligation,
+            target,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn decode_redeem_reserve_collateral() {
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+        let instruction = redeem_reserve_collateral(
+            crate::id(),
+            42,
+            keys[0], keys[1], keys[2], keys[3], keys[4], keys[5], keys[6], keys[7],
+        );
+        let decoded = decode_instruction(&instruction).unwrap();
+        assert_eq!(
+            decoded.instruction,
+            LendingInstruction::RedeemReserveCollateral {
+                collateral_amount: 42
+            }
+        );
+        assert_eq!(
+            decoded.accounts,
+            LendingInstructionAccounts::RedeemReserveCollateral(RedeemReserveCollateralAccounts {
+                source_collateral: keys[0],
+                reserve: keys[2],
+                reserve_collateral_mint: keys[3],
+                reserve_liquidity_supply: keys[4],
+                lending_market: keys[5],
+                lending_market_authority: keys[6],
+                user_transfer_authority: keys[7],
+                token_program: spl_token::id(),
+                destination: RedeemReserveCollateralDestination::Liquidity(keys[1]),
+            })
+        );
+
+        let mut accounts = instruction.accounts;
+        accounts.pop();
+        let bridge: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        accounts.extend(bridge.iter().map(|pubkey| AccountMeta::new(*pubkey, false)));
+        match decode(&instruction.data, &accounts).unwrap().accounts {
+            LendingInstructionAccounts::RedeemReserveCollateral(accounts) => assert_eq!(
+                accounts.destination,
+                RedeemReserveCollateralDestination::Bridge {
+                    bridge_pool: bridge[0],
+                    bridge_program: bridge[1],
+                    bridge_withdraw_lp: bridge[2],
+                }
+            ),
+            accounts => panic!("unexpected accounts {:?}", accounts),
+        }
+
+        accounts.pop();
+        assert_eq!(
+            decode(&instruction.data, &accounts),
+            Err(LendingError::InvalidAccountInput.into())
+        );
+    }
+
+    #[test]
+    fn decode_variable_accounts() {
+        let obligation = Pubkey::new_unique();
+        let reserves = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let decoded =
+            decode_instruction(&refresh_obligation(crate::id(), obligation, reserves.clone()))
+                .unwrap();
+        assert_eq!(
+            decoded.accounts,
+            LendingInstructionAccounts::RefreshObligation(RefreshObligationAccounts {
+                obligation,
+                reserves,
+            })
+        );
+
+        let config_accounts = vec![
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), true),
+        ];
+        let config_update = set_config(crate::id(), vec![20, 44, 1], config_accounts.clone());
+        assert_eq!(
+            decode_instruction(&config_update).unwrap().accounts,
+            LendingInstructionAccounts::SetConfig(SetConfigAccounts {
+                accounts: config_accounts,
+            })
+        );
+
+        let receiver_accounts = vec![AccountMeta::new(Pubkey::new_unique(), false)];
+        let keys: Vec<Pubkey> = (0..9).map(|_| Pubkey::new_unique()).collect();
+        let instruction = flash_loan(
+            crate::id(),
+            1_000,
+            keys[0],
+            keys[1],
+            keys[2],
+            keys[3],
+            None,
+            keys[4],
+            keys[5],
+            keys[6],
+            keys[7],
+            receiver_accounts.clone(),
+            vec![1, 2, 3],
+        );
+        match decode_instruction(&instruction).unwrap().accounts {
+            LendingInstructionAccounts::FlashLoan(accounts) => {
+                assert_eq!(accounts.host_fee_receiver, keys[3]);
+                assert_eq!(accounts.flash_loan_receiver_program, keys[6]);
+                assert_eq!(accounts.flash_loan_authority, keys[7]);
+                assert_eq!(accounts.flash_loan_receiver_accounts, receiver_accounts);
+            }
+            accounts => panic!("unexpected accounts {:?}", accounts),
+        }
+    }
+
+    #[test]
+    fn decode_claim_mine_layouts() {
+        let lending_market = LendingMarket {
+            mine_supply_account: Pubkey::new_unique(),
+            mine_lock_program: Pubkey::new_unique(),
+            max_claim_times: 200,
+            ..LendingMarket::default()
+        };
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+        let destination = ClaimMineTarget::Destination {
+            destination_pubkey: keys[5],
+        };
+        let lock_program = ClaimMineTarget::LockProgram {
+            lock_pool_pubkey: keys[6],
+            user_info_pubkey: keys[7],
+        };
+        let destination_accounts = ClaimMineTargetAccounts::Destination {
+            destination: keys[5],
+            source: lending_market.mine_supply_account,
+        };
+        let lock_program_accounts = ClaimMineTargetAccounts::LockProgram {
+            lock_program: lending_market.mine_lock_program,
+            lock_pool: keys[6],
+            user_info: keys[7],
+        };
+
+        for (obligation, target, target_accounts) in [
+            (None, destination, destination_accounts.clone()),
+            (Some(keys[4]), destination, destination_accounts),
+            (None, lock_program, lock_program_accounts.clone()),
+            (Some(keys[4]), lock_program, lock_program_accounts),
+        ] {
+            let instruction = claim_mine_with(obligation, target, &keys, &lending_market);
+            assert_eq!(
+                decode_instruction(&instruction).unwrap().accounts,
+                LendingInstructionAccounts::ClaimMine(ClaimMineAccounts {
+                    token_program: spl_token::id(),
+                    lending_market: keys[0],
+                    lending_market_authority: keys[1],
+                    owner: keys[2],
+                    mining: keys[3],
+                    obligation,
+                    target: target_accounts,
+                })
+            );
+        }
+
+        let mut instruction = claim_mine_with(Some(keys[4]), lock_program, &keys, &lending_market);
+        instruction
+            .accounts
+            .push(AccountMeta::new(Pubkey::new_unique(), false));
+        assert_eq!(
+            decode_instruction(&instruction),
+            Err(LendingError::InvalidAccountInput.into())
+        );
+    }
+
+    #[test]
+    fn decode_compiled() {
+        let obligation = Pubkey::new_unique();
+        let reserves = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let payer = Pubkey::new_unique();
+        let message = Message::new(
+            &[refresh_obligation(crate::id(), obligation, reserves.clone())],
+            Some(&payer),
+        );
+        let decoded = decode_compiled_instruction(&message, &message.instructions[0]).unwrap();
+        assert_eq!(decoded.instruction, LendingInstruction::RefreshObligation);
+        assert_eq!(
+            decoded.accounts,
+            LendingInstructionAccounts::RefreshObligation(RefreshObligationAccounts {
+                obligation,
+                reserves,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_missing_accounts() {
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+        let mut instruction = redeem_reserve_collateral(
+            crate::id(),
+            42,
+            keys[0], keys[1], keys[2], keys[3], keys[4], keys[5], keys[6], keys[7],
+        );
+        instruction.accounts.truncate(8);
+        assert_eq!(
+            decode_instruction(&instruction),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+        assert_eq!(
+            decode(&[255], &[]),
+            Err(ProgramError::from(LendingError::UnknownInstructionTag))
+        );
+    }
+    #[test]
+    fn borrow_obligation_liquidity_account_metas() {
+        let keys: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        let accounts = BorrowObligationLiquidityAccounts {
+            source_liquidity: keys[0],
+            destination_liquidity: keys[1],
+            borrow_reserve: keys[2],
+            obligation: keys[3],
+            lending_market: keys[4],
+            lending_market_authority: keys[5],
+            obligation_owner: keys[6],
+            token_program: spl_token::id(),
+            borrow_reserve_liquidity_fee_receiver: keys[7],
+            larix_oracle_program: keys[8],
+            mine_mint: keys[9],
+        };
+        let metas = accounts.to_account_metas();
+        assert_eq!(metas[6], AccountMeta::new_readonly(keys[6], true));
+        assert_eq!(metas[8], AccountMeta::new(keys[7], false));
+        assert_eq!(metas[9], AccountMeta::new_readonly(keys[8], false));
+
+        let instruction = borrow_obligation_liquidity(
+            crate::id(),
+            7,
+            keys[0], keys[1], keys[2], keys[7], keys[3], keys[4], keys[5], keys[6], keys[8], keys[9],
+        );
+        assert_eq!(instruction.accounts, metas);
+    }
+
+    #[test]
+    fn decoded_account_metas_round_trip() {
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+        let lending_market = LendingMarket {
+            mine_supply_account: Pubkey::new_unique(),
+            mine_lock_program: Pubkey::new_unique(),
+            max_claim_times: 200,
+            ..LendingMarket::default()
+        };
+        let mut instructions = vec![
+            refresh_obligation(crate::id(), keys[0], keys[1..4].to_vec()),
+            refresh_reserves(crate::id(), keys[0..2].to_vec(), keys[2..4].to_vec()),
+            set_config(
+                crate::id(),
+                vec![13, 1],
+                vec![
+                    AccountMeta::new(keys[0], false),
+                    AccountMeta::new_readonly(keys[1], true),
+                ],
+            ),
+            claim_obligation_mine(
+                crate::id(),
+                keys[0], keys[1], keys[2], keys[3], keys[4], keys[5],
+            ),
+            redeem_reserve_collateral(
+                crate::id(),
+                1,
+                keys[0], keys[1], keys[2], keys[3], keys[4], keys[5], keys[6], keys[7],
+            ),
+        ];
+        for target in [
+            ClaimMineTarget::Destination {
+                destination_pubkey: keys[5],
+            },
+            ClaimMineTarget::LockProgram {
+                lock_pool_pubkey: keys[6],
+                user_info_pubkey: keys[7],
+            },
+        ] {
+            instructions.push(claim_mine_with(None, target, &keys, &lending_market));
+            instructions.push(claim_mine_with(Some(keys[4]), target, &keys, &lending_market));
+        }
+        for instruction in instructions {
+            let decoded = decode_instruction(&instruction).unwrap();
+            assert_eq!(decoded.accounts.to_account_metas(), instruction.accounts);
+        }
+    }
+}
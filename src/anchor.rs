@@ -0,0 +1,903 @@
+//! Anchor integration
+//!
+//! Anchor is built against newer Solana crates than this program, so keys and account metas
+//! are converted through their byte representation.
+//!
+//! Anchor programs invoke the lending program with a `CpiContext` over one of the
+//! `*AccountInfos` structs and the `invoke` function.
+
+use crate::{
+    accounts::*,
+    instruction::LendingInstruction,
+    state::{
+        lending_market::LendingMarket, mining::Mining, obligation::Obligation, reserve::Reserve,
+    },
+};
+use anchor_lang::{
+    context::CpiContext,
+    solana_program::{
+        account_info::AccountInfo,
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+        pubkey::Pubkey,
+    },
+    AnchorSerialize, Discriminator, InstructionData, Owner, ToAccountInfos, ToAccountMetas,
+};
+use std::io::{Result, Write};
+
+/// Converts a key of this program's Solana version into an Anchor key
+pub fn to_anchor_pubkey(pubkey: &solana_program::pubkey::Pubkey) -> Pubkey {
+    Pubkey::new_from_array(pubkey.to_bytes())
+}
+
+/// Converts an Anchor key into a key of this program's Solana version
+pub fn from_anchor_pubkey(pubkey: &Pubkey) -> solana_program::pubkey::Pubkey {
+    solana_program::pubkey::Pubkey::new_from_array(pubkey.to_bytes())
+}
+
+/// The program dispatches on the tag byte, so the discriminator is empty
+impl Discriminator for LendingInstruction {
+    const DISCRIMINATOR: &'static [u8] = &[];
+}
+
+impl AnchorSerialize for LendingInstruction {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.pack())
+    }
+}
+
+impl InstructionData for LendingInstruction {
+    fn data(&self) -> Vec<u8> {
+        self.pack()
+    }
+}
+
+/// Like Anchor's client accounts, `is_signer` only overrides the accounts which sign the
+/// instruction
+fn to_anchor_account_metas(
+    account_metas: Vec<solana_program::instruction::AccountMeta>,
+    is_signer: Option<bool>,
+) -> Vec<AccountMeta> {
+    account_metas
+        .into_iter()
+        .map(|account_meta| AccountMeta {
+            pubkey: to_anchor_pubkey(&account_meta.pubkey),
+            is_signer: account_meta.is_signer && is_signer.unwrap_or(true),
+            is_writable: account_meta.is_writable,
+        })
+        .collect()
+}
+
+macro_rules! impl_to_account_metas {
+    ($($accounts:ty),* $(,)?) => {
+        $(
+            impl ToAccountMetas for $accounts {
+                fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+                    to_anchor_account_metas(<$accounts>::to_account_metas(self), is_signer)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_account_metas!(
+    InitLendingMarketAccounts,
+    SetLendingMarketOwnerAccounts,
+    InitReserveAccounts,
+    RefreshReserveAccounts,
+    DepositReserveLiquidityAccounts,
+    RedeemReserveCollateralAccounts,
+    InitObligationAccounts,
+    RefreshObligationAccounts,
+    DepositObligationCollateralAccounts,
+    WithdrawObligationCollateralAccounts,
+    BorrowObligationLiquidityAccounts,
+    RepayObligationLiquidityAccounts,
+    LiquidateObligationAccounts,
+    FlashLoanAccounts,
+    SetConfigAccounts,
+    InitMiningAccounts,
+    RefreshMiningAccounts,
+    DepositMiningAccounts,
+    WithdrawMiningAccounts,
+    ClaimMiningMineAccounts,
+    ClaimObligationMineAccounts,
+    ClaimOwnerFeeAccounts,
+    ReceivePendingOwnerAccounts,
+    RefreshReservesAccounts,
+    LiquidateObligation2Accounts,
+    ClaimMineAccounts,
+    LendingInstructionAccounts,
+);
+
+/// Invokes `instruction` with the accounts of `ctx`, signed by its `signer_seeds`.
+///
+/// The accounts must be the `*AccountInfos` of the instruction, for example
+/// `BorrowObligationLiquidityAccountInfos` for `LendingInstruction::BorrowObligationLiquidity`.
+pub fn invoke<'info, T: ToAccountMetas + ToAccountInfos<'info>>(
+    ctx: CpiContext<'_, '_, '_, 'info, T>,
+    instruction: LendingInstruction,
+) -> anchor_lang::Result<()> {
+    let instruction = Instruction {
+        program_id: ctx.program_id,
+        accounts: ctx.to_account_metas(None),
+        data: instruction.data(),
+    };
+    invoke_signed(&instruction, &ctx.to_account_infos(), ctx.signer_seeds).map_err(Into::into)
+}
+
+/// Keys of an `AccountInfo` field, as the field of the matching key struct
+trait AccountKeys<K> {
+    fn keys(&self) -> K;
+}
+
+/// `AccountInfo`s of a field, in account order
+trait AccountInfos<'info> {
+    fn extend_account_infos(&self, account_infos: &mut Vec<AccountInfo<'info>>);
+}
+
+impl AccountKeys<solana_program::pubkey::Pubkey> for AccountInfo<'_> {
+    fn keys(&self) -> solana_program::pubkey::Pubkey {
+        from_anchor_pubkey(self.key)
+    }
+}
+
+impl AccountKeys<solana_program::instruction::AccountMeta> for AccountInfo<'_> {
+    fn keys(&self) -> solana_program::instruction::AccountMeta {
+        solana_program::instruction::AccountMeta {
+            pubkey: from_anchor_pubkey(self.key),
+            is_signer: self.is_signer,
+            is_writable: self.is_writable,
+        }
+    }
+}
+
+impl<K, T: AccountKeys<K>> AccountKeys<Option<K>> for Option<T> {
+    fn keys(&self) -> Option<K> {
+        self.as_ref().map(AccountKeys::keys)
+    }
+}
+
+impl<K, T: AccountKeys<K>> AccountKeys<Vec<K>> for Vec<T> {
+    fn keys(&self) -> Vec<K> {
+        self.iter().map(AccountKeys::keys).collect()
+    }
+}
+
+impl<K, L, T: AccountKeys<K>, U: AccountKeys<L>> AccountKeys<(K, L)> for (T, U) {
+    fn keys(&self) -> (K, L) {
+        (self.0.keys(), self.1.keys())
+    }
+}
+
+impl<'info> AccountInfos<'info> for AccountInfo<'info> {
+    fn extend_account_infos(&self, account_infos: &mut Vec<AccountInfo<'info>>) {
+        account_infos.push(self.clone());
+    }
+}
+
+impl<'info, T: AccountInfos<'info>> AccountInfos<'info> for Option<T> {
+    fn extend_account_infos(&self, account_infos: &mut Vec<AccountInfo<'info>>) {
+        if let Some(infos) = self {
+            infos.extend_account_infos(account_infos);
+        }
+    }
+}
+
+impl<'info, T: AccountInfos<'info>> AccountInfos<'info> for Vec<T> {
+    fn extend_account_infos(&self, account_infos: &mut Vec<AccountInfo<'info>>) {
+        for infos in self {
+            infos.extend_account_infos(account_infos);
+        }
+    }
+}
+
+impl<'info, T: AccountInfos<'info>, U: AccountInfos<'info>> AccountInfos<'info> for (T, U) {
+    fn extend_account_infos(&self, account_infos: &mut Vec<AccountInfo<'info>>) {
+        self.0.extend_account_infos(account_infos);
+        self.1.extend_account_infos(account_infos);
+    }
+}
+
+/// Where the liquidity of a `RedeemReserveCollateral` invocation is paid to
+#[derive(Clone)]
+pub enum RedeemReserveCollateralDestinationInfos<'info> {
+    /// Destination liquidity token account
+    Liquidity(AccountInfo<'info>),
+    /// Withdraw the lp through the bridge program
+    Bridge {
+        /// Bridge pool info
+        bridge_pool: AccountInfo<'info>,
+        /// Bridge program id
+        bridge_program: AccountInfo<'info>,
+        /// Bridge withdraw lp account
+        bridge_withdraw_lp: AccountInfo<'info>,
+    },
+}
+
+impl AccountKeys<RedeemReserveCollateralDestination>
+    for RedeemReserveCollateralDestinationInfos<'_>
+{
+    fn keys(&self) -> RedeemReserveCollateralDestination {
+        match self {
+            Self::Liquidity(destination_liquidity) => {
+                RedeemReserveCollateralDestination::Liquidity(destination_liquidity.keys())
+            }
+            Self::Bridge {
+                bridge_pool,
+                bridge_program,
+                bridge_withdraw_lp,
+            } => RedeemReserveCollateralDestination::Bridge {
+                bridge_pool: bridge_pool.keys(),
+                bridge_program: bridge_program.keys(),
+                bridge_withdraw_lp: bridge_withdraw_lp.keys(),
+            },
+        }
+    }
+}
+
+impl<'info> AccountInfos<'info> for RedeemReserveCollateralDestinationInfos<'info> {
+    fn extend_account_infos(&self, account_infos: &mut Vec<AccountInfo<'info>>) {
+        match self {
+            Self::Liquidity(destination_liquidity) => {
+                destination_liquidity.extend_account_infos(account_infos);
+            }
+            Self::Bridge {
+                bridge_pool,
+                bridge_program,
+                bridge_withdraw_lp,
+            } => {
+                bridge_pool.extend_account_infos(account_infos);
+                bridge_program.extend_account_infos(account_infos);
+                bridge_withdraw_lp.extend_account_infos(account_infos);
+            }
+        }
+    }
+}
+
+/// Where the mine of a `ClaimMine` invocation is paid to
+#[derive(Clone)]
+pub enum ClaimMineTargetAccountInfos<'info> {
+    /// Transfer the mine to a token account
+    Destination {
+        /// Destination account
+        destination: AccountInfo<'info>,
+        /// Source account, the lending market mine supply
+        source: AccountInfo<'info>,
+    },
+    /// Lock the mine through the larix lock program
+    LockProgram {
+        /// Larix lock program
+        lock_program: AccountInfo<'info>,
+        /// Larix lock pool
+        lock_pool: AccountInfo<'info>,
+        /// User larix info account
+        user_info: AccountInfo<'info>,
+    },
+}
+
+impl AccountKeys<ClaimMineTargetAccounts> for ClaimMineTargetAccountInfos<'_> {
+    fn keys(&self) -> ClaimMineTargetAccounts {
+        match self {
+            Self::Destination {
+                destination,
+                source,
+            } => ClaimMineTargetAccounts::Destination {
+                destination: destination.keys(),
+                source: source.keys(),
+            },
+            Self::LockProgram {
+                lock_program,
+                lock_pool,
+                user_info,
+            } => ClaimMineTargetAccounts::LockProgram {
+                lock_program: lock_program.keys(),
+                lock_pool: lock_pool.keys(),
+                user_info: user_info.keys(),
+            },
+        }
+    }
+}
+
+impl<'info> AccountInfos<'info> for ClaimMineTargetAccountInfos<'info> {
+    fn extend_account_infos(&self, account_infos: &mut Vec<AccountInfo<'info>>) {
+        match self {
+            Self::Destination {
+                destination,
+                source,
+            } => {
+                destination.extend_account_infos(account_infos);
+                source.extend_account_infos(account_infos);
+            }
+            Self::LockProgram {
+                lock_program,
+                lock_pool,
+                user_info,
+            } => {
+                lock_program.extend_account_infos(account_infos);
+                lock_pool.extend_account_infos(account_infos);
+                user_info.extend_account_infos(account_infos);
+            }
+        }
+    }
+}
+
+/// Declares the `AccountInfo` struct of each key struct. Its account metas are the key struct's,
+/// so the writable and signer flags are only defined once
+macro_rules! account_infos {
+    ($(
+        $infos:ident<$info:lifetime> => $accounts:ident {
+            $($(#[$field_doc:meta])* $field:ident: $ty:ty,)*
+        }
+    )*) => {
+        $(
+            #[doc = concat!("`AccountInfo`s of [`", stringify!($accounts), "`], for a `CpiContext`")]
+            #[derive(Clone)]
+            pub struct $infos<$info> {
+                $($(#[$field_doc])* pub $field: $ty,)*
+            }
+
+            impl $infos<'_> {
+                /// Keys of the accounts
+                pub fn keys(&self) -> $accounts {
+                    $accounts {
+                        $($field: AccountKeys::keys(&self.$field),)*
+                    }
+                }
+            }
+
+            impl ToAccountMetas for $infos<'_> {
+                fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+                    ToAccountMetas::to_account_metas(&self.keys(), is_signer)
+                }
+            }
+
+            impl<$info> ToAccountInfos<$info> for $infos<$info> {
+                fn to_account_infos(&self) -> Vec<AccountInfo<$info>> {
+                    let mut account_infos = vec![];
+                    $(self.$field.extend_account_infos(&mut account_infos);)*
+                    account_infos
+                }
+            }
+        )*
+    };
+}
+
+account_infos! {
+    InitLendingMarketAccountInfos<'info> => InitLendingMarketAccounts {
+        /// Init lending market authority
+        init_lending_market_authority: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Rent sysvar
+        rent_sysvar: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+        /// Pyth oracle program id
+        oracle_program: AccountInfo<'info>,
+        /// Larix oracle program id
+        larix_oracle_program: AccountInfo<'info>,
+        /// Larix oracle id
+        larix_oracle: AccountInfo<'info>,
+    }
+    SetLendingMarketOwnerAccountInfos<'info> => SetLendingMarketOwnerAccounts {
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Current lending market owner
+        lending_market_owner: AccountInfo<'info>,
+    }
+    InitReserveAccountInfos<'info> => InitReserveAccounts {
+        /// Reserve account
+        reserve: AccountInfo<'info>,
+        /// Reserve liquidity SPL Token mint
+        liquidity_mint: AccountInfo<'info>,
+        /// Reserve liquidity supply SPL Token account
+        liquidity_supply: AccountInfo<'info>,
+        /// Reserve liquidity fee receiver
+        liquidity_fee_receiver: AccountInfo<'info>,
+        /// Pyth product account, any account when the liquidity is a lp
+        pyth_product: AccountInfo<'info>,
+        /// Pyth price account, BridgePool account when the liquidity is a lp
+        params_1: AccountInfo<'info>,
+        /// Larix oracle account, LpPrice account when the liquidity is a lp
+        params_2: AccountInfo<'info>,
+        /// Reserve collateral SPL Token mint
+        collateral_mint: AccountInfo<'info>,
+        /// Reserve collateral token supply
+        collateral_supply: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Lending market owner
+        lending_market_owner: AccountInfo<'info>,
+        /// Supply account of un-collateralized LTokens
+        un_coll_supply: AccountInfo<'info>,
+        /// Clock sysvar
+        clock_sysvar: AccountInfo<'info>,
+        /// Rent sysvar
+        rent_sysvar: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+    }
+    RefreshReserveAccountInfos<'info> => RefreshReserveAccounts {
+        /// Reserve account
+        reserve: AccountInfo<'info>,
+        /// Reserve liquidity oracle account
+        oracle: AccountInfo<'info>,
+        /// Larix oracle
+        larix_oracle: AccountInfo<'info>,
+    }
+    DepositReserveLiquidityAccountInfos<'info> => DepositReserveLiquidityAccounts {
+        /// Source liquidity token account
+        source_liquidity: AccountInfo<'info>,
+        /// Destination collateral token account
+        destination_collateral: AccountInfo<'info>,
+        /// Reserve account
+        reserve: AccountInfo<'info>,
+        /// Reserve collateral SPL Token mint
+        reserve_collateral_mint: AccountInfo<'info>,
+        /// Reserve liquidity supply SPL Token account
+        reserve_liquidity_supply: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// User transfer authority
+        user_transfer_authority: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+    }
+    RedeemReserveCollateralAccountInfos<'info> => RedeemReserveCollateralAccounts {
+        /// Source collateral token account
+        source_collateral: AccountInfo<'info>,
+        /// Reserve account
+        reserve: AccountInfo<'info>,
+        /// Reserve collateral SPL Token mint
+        reserve_collateral_mint: AccountInfo<'info>,
+        /// Reserve liquidity supply SPL Token account
+        reserve_liquidity_supply: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// User transfer authority
+        user_transfer_authority: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+        /// Destination of the redeemed liquidity
+        destination: RedeemReserveCollateralDestinationInfos<'info>,
+    }
+    InitObligationAccountInfos<'info> => InitObligationAccounts {
+        /// Obligation account
+        obligation: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Obligation owner
+        obligation_owner: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+    }
+    RefreshObligationAccountInfos<'info> => RefreshObligationAccounts {
+        /// Obligation account
+        obligation: AccountInfo<'info>,
+        /// Deposit reserves followed by borrow reserves, in order
+        reserves: Vec<AccountInfo<'info>>,
+    }
+    DepositObligationCollateralAccountInfos<'info> => DepositObligationCollateralAccounts {
+        /// Source collateral token account
+        source_collateral: AccountInfo<'info>,
+        /// Destination deposit reserve collateral supply SPL Token account
+        destination_collateral: AccountInfo<'info>,
+        /// Deposit reserve account
+        deposit_reserve: AccountInfo<'info>,
+        /// Obligation account
+        obligation: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// Obligation owner
+        obligation_owner: AccountInfo<'info>,
+        /// User transfer authority
+        user_transfer_authority: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+        /// Reserves of the obligation
+        reserves: Vec<AccountInfo<'info>>,
+    }
+    WithdrawObligationCollateralAccountInfos<'info> => WithdrawObligationCollateralAccounts {
+        /// Source withdraw reserve collateral supply SPL Token account
+        source_collateral: AccountInfo<'info>,
+        /// Destination collateral token account
+        destination_collateral: AccountInfo<'info>,
+        /// Withdraw reserve account
+        withdraw_reserve: AccountInfo<'info>,
+        /// Obligation account
+        obligation: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// Obligation owner
+        obligation_owner: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+    }
+    BorrowObligationLiquidityAccountInfos<'info> => BorrowObligationLiquidityAccounts {
+        /// Source borrow reserve liquidity supply SPL Token account
+        source_liquidity: AccountInfo<'info>,
+        /// Destination liquidity token account
+        destination_liquidity: AccountInfo<'info>,
+        /// Borrow reserve account
+        borrow_reserve: AccountInfo<'info>,
+        /// Obligation account
+        obligation: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// Obligation owner
+        obligation_owner: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+        /// Borrow reserve liquidity fee receiver
+        borrow_reserve_liquidity_fee_receiver: AccountInfo<'info>,
+        /// Larix oracle program account
+        larix_oracle_program: AccountInfo<'info>,
+        /// Mine mint account
+        mine_mint: AccountInfo<'info>,
+    }
+    RepayObligationLiquidityAccountInfos<'info> => RepayObligationLiquidityAccounts {
+        /// Source liquidity token account
+        source_liquidity: AccountInfo<'info>,
+        /// Destination repay reserve liquidity supply SPL Token account
+        destination_liquidity: AccountInfo<'info>,
+        /// Repay reserve account
+        repay_reserve: AccountInfo<'info>,
+        /// Obligation account
+        obligation: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// User transfer authority
+        user_transfer_authority: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+    }
+    LiquidateObligationAccountInfos<'info> => LiquidateObligationAccounts {
+        /// Source liquidity token account
+        source_liquidity: AccountInfo<'info>,
+        /// Destination collateral token account
+        destination_collateral: AccountInfo<'info>,
+        /// Repay reserve account
+        repay_reserve: AccountInfo<'info>,
+        /// Repay reserve liquidity supply SPL Token account
+        repay_reserve_liquidity_supply: AccountInfo<'info>,
+        /// Withdraw reserve account
+        withdraw_reserve: AccountInfo<'info>,
+        /// Withdraw reserve collateral supply SPL Token account
+        withdraw_reserve_collateral_supply: AccountInfo<'info>,
+        /// Obligation account
+        obligation: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// User transfer authority
+        user_transfer_authority: AccountInfo<'info>,
+        /// Clock sysvar
+        clock_sysvar: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+    }
+    FlashLoanAccountInfos<'info> => FlashLoanAccounts {
+        /// Source reserve liquidity supply SPL Token account
+        source_liquidity: AccountInfo<'info>,
+        /// Destination liquidity token account
+        destination_liquidity: AccountInfo<'info>,
+        /// Reserve account
+        reserve: AccountInfo<'info>,
+        /// Flash loan fee receiver account
+        flash_loan_fee_receiver: AccountInfo<'info>,
+        /// Host fee receiver
+        host_fee_receiver: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+        /// Flash loan receiver program id
+        flash_loan_receiver_program: AccountInfo<'info>,
+        /// Flash loan authority
+        flash_loan_authority: AccountInfo<'info>,
+        /// Additional accounts forwarded to the flash loan receiver program
+        flash_loan_receiver_accounts: Vec<AccountInfo<'info>>,
+    }
+    SetConfigAccountInfos<'info> => SetConfigAccounts {
+        /// Accounts in the layout of the deployed program, passed through as is
+        accounts: Vec<AccountInfo<'info>>,
+    }
+    InitMiningAccountInfos<'info> => InitMiningAccounts {
+        /// Mining account
+        mining: AccountInfo<'info>,
+        /// Mining owner
+        mining_owner: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+    }
+    RefreshMiningAccountInfos<'info> => RefreshMiningAccounts {
+        /// Mining account
+        mining: AccountInfo<'info>,
+        /// Reserves in mining
+        reserves: Vec<AccountInfo<'info>>,
+    }
+    DepositMiningAccountInfos<'info> => DepositMiningAccounts {
+        /// Source collateral token account
+        source_collateral: AccountInfo<'info>,
+        /// UnColl deposit supply SPL Token account
+        un_coll_supply: AccountInfo<'info>,
+        /// Mining account
+        mining: AccountInfo<'info>,
+        /// Reserve account holding the bonus
+        reserve: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Mining owner
+        mining_owner: AccountInfo<'info>,
+        /// User transfer authority
+        user_transfer_authority: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+    }
+    WithdrawMiningAccountInfos<'info> => WithdrawMiningAccounts {
+        /// Destination collateral token account
+        destination_collateral: AccountInfo<'info>,
+        /// UnColl deposit supply SPL Token account
+        un_coll_supply: AccountInfo<'info>,
+        /// Mining account
+        mining: AccountInfo<'info>,
+        /// Reserve account
+        reserve: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// Mining owner
+        mining_owner: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+    }
+    ClaimMiningMineAccountInfos<'info> => ClaimMiningMineAccounts {
+        /// Mining account
+        mining: AccountInfo<'info>,
+        /// Mine supply
+        mine_supply: AccountInfo<'info>,
+        /// Destination account
+        destination: AccountInfo<'info>,
+        /// Mining owner
+        mining_owner: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+        /// Reserves in mining
+        reserves: Vec<AccountInfo<'info>>,
+    }
+    ClaimObligationMineAccountInfos<'info> => ClaimObligationMineAccounts {
+        /// Obligation account
+        obligation: AccountInfo<'info>,
+        /// Mine supply
+        mine_supply: AccountInfo<'info>,
+        /// Destination account
+        destination: AccountInfo<'info>,
+        /// Obligation owner
+        obligation_owner: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+    }
+    ClaimOwnerFeeAccountInfos<'info> => ClaimOwnerFeeAccounts {
+        /// Reserve liquidity supply account
+        reserve_liquidity_supply: AccountInfo<'info>,
+        /// Destination account receiving the owner fee
+        destination: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Lending market owner
+        lending_market_owner: AccountInfo<'info>,
+    }
+    ReceivePendingOwnerAccountInfos<'info> => ReceivePendingOwnerAccounts {
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Pending owner
+        pending_owner: AccountInfo<'info>,
+    }
+    RefreshReservesAccountInfos<'info> => RefreshReservesAccounts {
+        /// Reserve accounts, each with its oracle account: larix oracle or pyth price account
+        reserves: Vec<(AccountInfo<'info>, AccountInfo<'info>)>,
+    }
+    LiquidateObligation2AccountInfos<'info> => LiquidateObligation2Accounts {
+        /// Source liquidity token account
+        source_liquidity: AccountInfo<'info>,
+        /// Destination collateral token account
+        destination_collateral: AccountInfo<'info>,
+        /// Repay reserve account
+        repay_reserve: AccountInfo<'info>,
+        /// Repay reserve liquidity supply SPL Token account
+        repay_reserve_liquidity_supply: AccountInfo<'info>,
+        /// Withdraw reserve account
+        withdraw_reserve: AccountInfo<'info>,
+        /// Withdraw reserve collateral supply SPL Token account
+        withdraw_reserve_collateral_supply: AccountInfo<'info>,
+        /// Obligation account
+        obligation: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// User transfer authority
+        user_transfer_authority: AccountInfo<'info>,
+        /// Token program id
+        token_program: AccountInfo<'info>,
+    }
+    ClaimMineAccountInfos<'info> => ClaimMineAccounts {
+        /// Token program id
+        token_program: AccountInfo<'info>,
+        /// Lending market account
+        lending_market: AccountInfo<'info>,
+        /// Derived lending market authority
+        lending_market_authority: AccountInfo<'info>,
+        /// Owner of the mining and obligation accounts
+        owner: AccountInfo<'info>,
+        /// Mining account
+        mining: AccountInfo<'info>,
+        /// Obligation account, if the owner has one
+        obligation: Option<AccountInfo<'info>>,
+        /// Where the mine is paid to
+        target: ClaimMineTargetAccountInfos<'info>,
+    }
+}
+
+impl Owner for LendingMarket {
+    fn owner() -> Pubkey {
+        to_anchor_pubkey(&crate::id())
+    }
+}
+
+impl Owner for Reserve {
+    fn owner() -> Pubkey {
+        to_anchor_pubkey(&crate::id())
+    }
+}
+
+impl Owner for Obligation {
+    fn owner() -> Pubkey {
+        to_anchor_pubkey(&crate::id())
+    }
+}
+
+impl Owner for Mining {
+    fn owner() -> Pubkey {
+        to_anchor_pubkey(&crate::id())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::instruction::{borrow_obligation_liquidity, refresh_reserves};
+
+    #[test]
+    fn instruction_data_is_packed_instruction() {
+        let instruction = LendingInstruction::DepositReserveLiquidity {
+            liquidity_amount: 1_000,
+        };
+        assert_eq!(instruction.data(), instruction.pack());
+        let mut data = vec![];
+        instruction.write_to(&mut data);
+        assert_eq!(data, instruction.pack());
+    }
+
+    #[test]
+    fn anchor_account_metas() {
+        let keys: Vec<solana_program::pubkey::Pubkey> = (0..10)
+            .map(|_| solana_program::pubkey::Pubkey::new_unique())
+            .collect();
+        let instruction = borrow_obligation_liquidity(
+            crate::id(),
+            1,
+            keys[0], keys[1], keys[2], keys[3], keys[4], keys[5], keys[6], keys[7], keys[8], keys[9],
+        );
+        let accounts = crate::accounts::decode_instruction(&instruction)
+            .unwrap()
+            .accounts;
+
+        let account_metas = ToAccountMetas::to_account_metas(&accounts, None);
+        assert_eq!(account_metas.len(), instruction.accounts.len());
+        for (anchor_meta, meta) in account_metas.iter().zip(instruction.accounts.iter()) {
+            assert_eq!(from_anchor_pubkey(&anchor_meta.pubkey), meta.pubkey);
+            assert_eq!(anchor_meta.is_signer, meta.is_signer);
+            assert_eq!(anchor_meta.is_writable, meta.is_writable);
+        }
+
+        let relayed = ToAccountMetas::to_account_metas(&accounts, Some(false));
+        assert!(relayed.iter().all(|account_meta| !account_meta.is_signer));
+        assert_eq!(
+            <LendingMarket as Owner>::owner(),
+            to_anchor_pubkey(&crate::id())
+        );
+    }
+
+    #[test]
+    fn cpi_context_accounts() {
+        let mut keys: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        keys.push(to_anchor_pubkey(&spl_token::id()));
+        let owner = Pubkey::new_unique();
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![[0u8; 0]; keys.len()];
+        let infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &owner, false)
+            })
+            .collect();
+
+        let accounts = BorrowObligationLiquidityAccountInfos {
+            source_liquidity: infos[0].clone(),
+            destination_liquidity: infos[1].clone(),
+            borrow_reserve: infos[2].clone(),
+            obligation: infos[4].clone(),
+            lending_market: infos[5].clone(),
+            lending_market_authority: infos[6].clone(),
+            obligation_owner: infos[7].clone(),
+            token_program: infos[10].clone(),
+            borrow_reserve_liquidity_fee_receiver: infos[3].clone(),
+            larix_oracle_program: infos[8].clone(),
+            mine_mint: infos[9].clone(),
+        };
+        let keys: Vec<solana_program::pubkey::Pubkey> =
+            keys.iter().map(from_anchor_pubkey).collect();
+        let instruction = borrow_obligation_liquidity(
+            crate::id(),
+            1,
+            keys[0], keys[1], keys[2], keys[3], keys[4], keys[5], keys[6], keys[7], keys[8], keys[9],
+        );
+
+        let ctx = CpiContext::new(to_anchor_pubkey(&crate::id()), accounts);
+        let account_metas = ctx.to_account_metas(None);
+        assert_eq!(account_metas.len(), instruction.accounts.len());
+        for (anchor_meta, meta) in account_metas.iter().zip(instruction.accounts.iter()) {
+            assert_eq!(from_anchor_pubkey(&anchor_meta.pubkey), meta.pubkey);
+            assert_eq!(anchor_meta.is_signer, meta.is_signer);
+            assert_eq!(anchor_meta.is_writable, meta.is_writable);
+        }
+        assert_eq!(ctx.to_account_infos().len(), instruction.accounts.len());
+
+        let accounts = RefreshReservesAccountInfos {
+            reserves: vec![
+                (infos[0].clone(), infos[1].clone()),
+                (infos[2].clone(), infos[3].clone()),
+            ],
+        };
+        let instruction =
+            refresh_reserves(crate::id(), vec![keys[0], keys[2]], vec![keys[1], keys[3]]);
+        let account_metas = ToAccountMetas::to_account_metas(&accounts, None);
+        let account_infos = accounts.to_account_infos();
+        assert_eq!(account_metas.len(), instruction.accounts.len());
+        for ((anchor_meta, account_info), meta) in account_metas
+            .iter()
+            .zip(account_infos.iter())
+            .zip(instruction.accounts.iter())
+        {
+            assert_eq!(from_anchor_pubkey(&anchor_meta.pubkey), meta.pubkey);
+            assert_eq!(from_anchor_pubkey(account_info.key), meta.pubkey);
+            assert_eq!(anchor_meta.is_writable, meta.is_writable);
+        }
+    }
+}
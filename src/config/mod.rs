@@ -0,0 +1,143 @@
+//! Reserve parameters of the deployment config files in `config/`
+
+use crate::state::reserve::{Bonus, InitBonusParams, ReserveConfig, ReserveFees};
+use serde::{de::Error, Deserialize, Deserializer};
+use solana_program::pubkey::Pubkey;
+use std::collections::BTreeMap;
+
+/// Deployment config file of a lending market
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct LendingMarketJsonConfig {
+    /// Reserve parameters by liquidity mint address
+    pub reserves: BTreeMap<String, ReserveJsonConfig>,
+}
+
+/// Parameters of a reserve in a deployment config file
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct ReserveJsonConfig {
+    /// Name of the reserve
+    pub name: String,
+    /// Optimal utilization rate, as a percentage
+    pub optimal_utilization_rate: u8,
+    /// Target ratio of the value of borrows to deposits, as a percentage
+    pub loan_to_value_ratio: u8,
+    /// Bonus a liquidator gets when repaying part of an unhealthy obligation, as a percentage
+    pub liquidation_bonus: u8,
+    /// Loan to value ratio at which an obligation can be liquidated, as a percentage
+    pub liquidation_threshold: u8,
+    /// Min borrow APY, as a percentage
+    pub min_borrow_rate: u8,
+    /// Optimal (utilization) borrow APY, as a percentage
+    pub optimal_borrow_rate: u8,
+    /// Max borrow APY, as a percentage
+    pub max_borrow_rate: u8,
+    /// Fee assessed on `BorrowObligationLiquidity`, expressed as a Wad
+    pub borrow_fee_wad: u64,
+    /// Reserve owner fee on interest, expressed as a Wad
+    pub borrow_interest_fee_wad: u64,
+    /// Fee for flash loan, expressed as a Wad
+    pub flash_loan_fee_wad: u64,
+    /// Amount of fee going to host account, as a percentage
+    pub host_fee_percentage: u8,
+    /// If deposit paused
+    pub deposit_paused: bool,
+    /// If borrow paused
+    pub borrow_paused: bool,
+    /// If liquidation paused
+    pub liquidation_paused: bool,
+    /// Amount of mine token for this reserve per slot
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub total_mining_speed: u64,
+    /// Utilization rate at which the mine distribution curve kinks, as a percentage.
+    ///
+    /// Sent as the `kink_util_rate` of `InitReserve` and stored as `Bonus::supply_rate`. It is
+    /// also the `ReserveConfig::kink_util_rate` the borrow rate curve changes slope at
+    pub kink_util_rate: u64,
+    /// Is the liquidity mint a lp
+    #[serde(default)]
+    pub is_lp: bool,
+}
+
+impl ReserveJsonConfig {
+    /// Reserve configuration values, the way `InitReserve` sets them
+    pub fn reserve_config(&self) -> ReserveConfig {
+        ReserveConfig {
+            optimal_utilization_rate: self.optimal_utilization_rate,
+            loan_to_value_ratio: self.loan_to_value_ratio,
+            liquidation_bonus: self.liquidation_bonus,
+            liquidation_threshold: self.liquidation_threshold,
+            min_borrow_rate: self.min_borrow_rate,
+            optimal_borrow_rate: self.optimal_borrow_rate,
+            max_borrow_rate: self.max_borrow_rate,
+            fees: ReserveFees {
+                borrow_fee_wad: self.borrow_fee_wad,
+                reserve_owner_fee_wad: self.borrow_interest_fee_wad,
+                flash_loan_fee_wad: self.flash_loan_fee_wad,
+                host_fee_percentage: self.host_fee_percentage,
+                host_fee_receivers: vec![],
+            },
+            deposit_paused: self.deposit_paused,
+            borrow_paused: self.borrow_paused,
+            liquidation_paused: self.liquidation_paused,
+            kink_util_rate: self.kink_util_rate,
+            ..ReserveConfig::default()
+        }
+    }
+
+    /// Mining info of the reserve, `kink_util_rate` becomes the `supply_rate`
+    pub fn bonus(&self, un_coll_supply_account: Pubkey) -> Bonus {
+        Bonus::new(InitBonusParams {
+            un_coll_supply_account,
+            total_mining_speed: self.total_mining_speed,
+            supply_rate: self.kink_util_rate,
+        })
+    }
+}
+
+fn deserialize_u64_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::Rate;
+
+    fn lending_market_config(json: &str) -> LendingMarketJsonConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn product_config() {
+        let config = lending_market_config(include_str!("../../config/config-product.json"));
+        let usdt = &config.reserves["Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB"];
+        assert_eq!(usdt.name, "USDT");
+        assert_eq!(usdt.total_mining_speed, 46);
+        assert_eq!(usdt.kink_util_rate, 50);
+
+        let reserve_config = usdt.reserve_config();
+        assert_eq!(reserve_config.optimal_utilization_rate, 90);
+        assert_eq!(reserve_config.max_borrow_rate, 47);
+        assert_eq!(reserve_config.kink_utilization_rate(), 50);
+        assert_eq!(
+            reserve_config.fees.reserve_owner_fee_wad,
+            200_000_000_000_000_000
+        );
+        assert_eq!(
+            usdt.bonus(Pubkey::default()).kink_util_rate(),
+            Rate::from_percent(50)
+        );
+    }
+
+    #[test]
+    fn kink_util_rate_over_100() {
+        let config = lending_market_config(include_str!("../../config/config-test.json"));
+        // RAY is configured with a kink_util_rate of 5000
+        let ray = &config.reserves["4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R"];
+        assert_eq!(ray.kink_util_rate, 5000);
+        assert_eq!(ray.bonus(Pubkey::default()).kink_util_rate(), Rate::one());
+        assert_eq!(ray.reserve_config().kink_utilization_rate(), 100);
+    }
+}
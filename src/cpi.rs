@@ -0,0 +1,848 @@
+//! Cross-program invocations of the lending program
+//!
+//! Each instruction has an `invoke` wrapper and a `_signed` wrapper taking the `signers_seeds`
+//! of the program derived addresses signing the instruction.
+
+use crate::{
+    accounts::{
+        BorrowObligationLiquidityAccounts, ClaimMineAccounts, ClaimMineTargetAccounts,
+        DepositObligationCollateralAccounts, DepositReserveLiquidityAccounts,
+        InitObligationAccounts, RedeemReserveCollateralAccounts,
+        RedeemReserveCollateralDestination, RefreshObligationAccounts, RefreshReservesAccounts,
+        RepayObligationLiquidityAccounts, WithdrawObligationCollateralAccounts,
+    },
+    instruction::LendingInstruction,
+    state::lending_market::LendingMarket,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+/// Where the mine of a `ClaimMine` invocation is paid to
+pub enum ClaimMineTargetInfos<'a> {
+    /// Transfer the mine to a token account
+    Destination {
+        /// Destination account
+        destination: AccountInfo<'a>,
+        /// Source account, the lending market mine supply
+        source: AccountInfo<'a>,
+    },
+    /// Lock the mine through the larix lock program
+    LockProgram {
+        /// Larix lock program
+        lock_program: AccountInfo<'a>,
+        /// Larix lock pool
+        lock_pool: AccountInfo<'a>,
+        /// User larix info account
+        user_info: AccountInfo<'a>,
+    },
+}
+
+fn invoke_lending(
+    program_id: &Pubkey,
+    accounts: Vec<AccountMeta>,
+    instruction: LendingInstruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts,
+        data: instruction.pack(),
+    };
+    invoke_signed(&instruction, account_infos, signers_seeds)
+}
+
+fn keys(account_infos: &[AccountInfo]) -> Vec<Pubkey> {
+    account_infos.iter().map(|account_info| *account_info.key).collect()
+}
+
+/// Invokes a `RefreshReserves` instruction, each reserve is paired with its oracle account
+pub fn refresh_reserves<'a>(
+    program_id: &Pubkey,
+    reserves: &[(AccountInfo<'a>, AccountInfo<'a>)],
+) -> ProgramResult {
+    refresh_reserves_signed(program_id, reserves, &[])
+}
+
+/// Invokes a `RefreshReserves` instruction signed by `signers_seeds`
+pub fn refresh_reserves_signed<'a>(
+    program_id: &Pubkey,
+    reserves: &[(AccountInfo<'a>, AccountInfo<'a>)],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = RefreshReservesAccounts {
+        reserves: reserves
+            .iter()
+            .map(|(reserve, oracle)| (*reserve.key, *oracle.key))
+            .collect(),
+    };
+    let account_infos: Vec<AccountInfo<'a>> = reserves
+        .iter()
+        .flat_map(|(reserve, oracle)| vec![reserve.clone(), oracle.clone()])
+        .collect();
+    invoke_lending(
+        program_id,
+        accounts.to_account_metas(),
+        LendingInstruction::RefreshReserves,
+        &account_infos,
+        signers_seeds,
+    )
+}
+
+/// Invokes a `DepositReserveLiquidity` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_reserve_liquidity<'a>(
+    program_id: &Pubkey,
+    liquidity_amount: u64,
+    source_liquidity: AccountInfo<'a>,
+    destination_collateral: AccountInfo<'a>,
+    reserve: AccountInfo<'a>,
+    reserve_collateral_mint: AccountInfo<'a>,
+    reserve_liquidity_supply: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    user_transfer_authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+) -> ProgramResult {
+    deposit_reserve_liquidity_signed(
+        program_id,
+        liquidity_amount,
+        source_liquidity,
+        destination_collateral,
+        reserve,
+        reserve_collateral_mint,
+        reserve_liquidity_supply,
+        lending_market,
+        lending_market_authority,
+        user_transfer_authority,
+        token_program,
+        &[],
+    )
+}
+
+/// Invokes a `DepositReserveLiquidity` instruction signed by `signers_seeds`
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_reserve_liquidity_signed<'a>(
+    program_id: &Pubkey,
+    liquidity_amount: u64,
+    source_liquidity: AccountInfo<'a>,
+    destination_collateral: AccountInfo<'a>,
+    reserve: AccountInfo<'a>,
+    reserve_collateral_mint: AccountInfo<'a>,
+    reserve_liquidity_supply: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    user_transfer_authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = DepositReserveLiquidityAccounts {
+        source_liquidity: *source_liquidity.key,
+        destination_collateral: *destination_collateral.key,
+        reserve: *reserve.key,
+        reserve_collateral_mint: *reserve_collateral_mint.key,
+        reserve_liquidity_supply: *reserve_liquidity_supply.key,
+        lending_market: *lending_market.key,
+        lending_market_authority: *lending_market_authority.key,
+        user_transfer_authority: *user_transfer_authority.key,
+        token_program: *token_program.key,
+    };
+    invoke_lending(
+        program_id,
+        accounts.to_account_metas(),
+        LendingInstruction::DepositReserveLiquidity { liquidity_amount },
+        &[
+            source_liquidity,
+            destination_collateral,
+            reserve,
+            reserve_collateral_mint,
+            reserve_liquidity_supply,
+            lending_market,
+            lending_market_authority,
+            user_transfer_authority,
+            token_program,
+        ],
+        signers_seeds,
+    )
+}
+
+/// Invokes a `RedeemReserveCollateral` instruction paying the liquidity to a token account
+#[allow(clippy::too_many_arguments)]
+pub fn redeem_reserve_collateral<'a>(
+    program_id: &Pubkey,
+    collateral_amount: u64,
+    source_collateral: AccountInfo<'a>,
+    destination_liquidity: AccountInfo<'a>,
+    reserve: AccountInfo<'a>,
+    reserve_collateral_mint: AccountInfo<'a>,
+    reserve_liquidity_supply: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    user_transfer_authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+) -> ProgramResult {
+    redeem_reserve_collateral_signed(
+        program_id,
+        collateral_amount,
+        source_collateral,
+        destination_liquidity,
+        reserve,
+        reserve_collateral_mint,
+        reserve_liquidity_supply,
+        lending_market,
+        lending_market_authority,
+        user_transfer_authority,
+        token_program,
+        &[],
+    )
+}
+
+/// Invokes a `RedeemReserveCollateral` instruction signed by `signers_seeds`
+#[allow(clippy::too_many_arguments)]
+pub fn redeem_reserve_collateral_signed<'a>(
+    program_id: &Pubkey,
+    collateral_amount: u64,
+    source_collateral: AccountInfo<'a>,
+    destination_liquidity: AccountInfo<'a>,
+    reserve: AccountInfo<'a>,
+    reserve_collateral_mint: AccountInfo<'a>,
+    reserve_liquidity_supply: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    user_transfer_authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = RedeemReserveCollateralAccounts {
+        source_collateral: *source_collateral.key,
+        reserve: *reserve.key,
+        reserve_collateral_mint: *reserve_collateral_mint.key,
+        reserve_liquidity_supply: *reserve_liquidity_supply.key,
+        lending_market: *lending_market.key,
+        lending_market_authority: *lending_market_authority.key,
+        user_transfer_authority: *user_transfer_authority.key,
+        token_program: *token_program.key,
+        destination: RedeemReserveCollateralDestination::Liquidity(*destination_liquidity.key),
+    };
+    invoke_lending(
+        program_id,
+        accounts.to_account_metas(),
+        LendingInstruction::RedeemReserveCollateral { collateral_amount },
+        &[
+            source_collateral,
+            reserve,
+            reserve_collateral_mint,
+            reserve_liquidity_supply,
+            lending_market,
+            lending_market_authority,
+            user_transfer_authority,
+            token_program,
+            destination_liquidity,
+        ],
+        signers_seeds,
+    )
+}
+
+/// Invokes an `InitObligation` instruction
+pub fn init_obligation<'a>(
+    program_id: &Pubkey,
+    obligation: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    obligation_owner: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+) -> ProgramResult {
+    init_obligation_signed(
+        program_id,
+        obligation,
+        lending_market,
+        obligation_owner,
+        token_program,
+        &[],
+    )
+}
+
+/// Invokes an `InitObligation` instruction signed by `signers_seeds`
+pub fn init_obligation_signed<'a>(
+    program_id: &Pubkey,
+    obligation: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    obligation_owner: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = InitObligationAccounts {
+        obligation: *obligation.key,
+        lending_market: *lending_market.key,
+        obligation_owner: *obligation_owner.key,
+        token_program: *token_program.key,
+    };
+    invoke_lending(
+        program_id,
+        accounts.to_account_metas(),
+        LendingInstruction::InitObligation,
+        &[obligation, lending_market, obligation_owner, token_program],
+        signers_seeds,
+    )
+}
+
+/// Invokes a `RefreshObligation` instruction, `reserves` are the deposit reserves followed by
+/// the borrow reserves of the obligation
+pub fn refresh_obligation<'a>(
+    program_id: &Pubkey,
+    obligation: AccountInfo<'a>,
+    reserves: &[AccountInfo<'a>],
+) -> ProgramResult {
+    refresh_obligation_signed(program_id, obligation, reserves, &[])
+}
+
+/// Invokes a `RefreshObligation` instruction signed by `signers_seeds`
+pub fn refresh_obligation_signed<'a>(
+    program_id: &Pubkey,
+    obligation: AccountInfo<'a>,
+    reserves: &[AccountInfo<'a>],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = RefreshObligationAccounts {
+        obligation: *obligation.key,
+        reserves: keys(reserves),
+    };
+    let mut account_infos = vec![obligation];
+    account_infos.extend_from_slice(reserves);
+    invoke_lending(
+        program_id,
+        accounts.to_account_metas(),
+        LendingInstruction::RefreshObligation,
+        &account_infos,
+        signers_seeds,
+    )
+}
+
+/// Invokes a `DepositObligationCollateral` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_obligation_collateral<'a>(
+    program_id: &Pubkey,
+    collateral_amount: u64,
+    source_collateral: AccountInfo<'a>,
+    destination_collateral: AccountInfo<'a>,
+    deposit_reserve: AccountInfo<'a>,
+    obligation: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    obligation_owner: AccountInfo<'a>,
+    user_transfer_authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    reserves: &[AccountInfo<'a>],
+) -> ProgramResult {
+    deposit_obligation_collateral_signed(
+        program_id,
+        collateral_amount,
+        source_collateral,
+        destination_collateral,
+        deposit_reserve,
+        obligation,
+        lending_market,
+        lending_market_authority,
+        obligation_owner,
+        user_transfer_authority,
+        token_program,
+        reserves,
+        &[],
+    )
+}
+
+/// Invokes a `DepositObligationCollateral` instruction signed by `signers_seeds`
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_obligation_collateral_signed<'a>(
+    program_id: &Pubkey,
+    collateral_amount: u64,
+    source_collateral: AccountInfo<'a>,
+    destination_collateral: AccountInfo<'a>,
+    deposit_reserve: AccountInfo<'a>,
+    obligation: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    obligation_owner: AccountInfo<'a>,
+    user_transfer_authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    reserves: &[AccountInfo<'a>],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = DepositObligationCollateralAccounts {
+        source_collateral: *source_collateral.key,
+        destination_collateral: *destination_collateral.key,
+        deposit_reserve: *deposit_reserve.key,
+        obligation: *obligation.key,
+        lending_market: *lending_market.key,
+        lending_market_authority: *lending_market_authority.key,
+        obligation_owner: *obligation_owner.key,
+        user_transfer_authority: *user_transfer_authority.key,
+        token_program: *token_program.key,
+        reserves: keys(reserves),
+    };
+    let mut account_infos = vec![
+        source_collateral,
+        destination_collateral,
+        deposit_reserve,
+        obligation,
+        lending_market,
+        lending_market_authority,
+        obligation_owner,
+        user_transfer_authority,
+        token_program,
+    ];
+    account_infos.extend_from_slice(reserves);
+    invoke_lending(
+        program_id,
+        accounts.to_account_metas(),
+        LendingInstruction::DepositObligationCollateral { collateral_amount },
+        &account_infos,
+        signers_seeds,
+    )
+}
+
+/// Invokes a `WithdrawObligationCollateral` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_obligation_collateral<'a>(
+    program_id: &Pubkey,
+    collateral_amount: u64,
+    source_collateral: AccountInfo<'a>,
+    destination_collateral: AccountInfo<'a>,
+    withdraw_reserve: AccountInfo<'a>,
+    obligation: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    obligation_owner: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+) -> ProgramResult {
+    withdraw_obligation_collateral_signed(
+        program_id,
+        collateral_amount,
+        source_collateral,
+        destination_collateral,
+        withdraw_reserve,
+        obligation,
+        lending_market,
+        lending_market_authority,
+        obligation_owner,
+        token_program,
+        &[],
+    )
+}
+
+/// Invokes a `WithdrawObligationCollateral` instruction signed by `signers_seeds`
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_obligation_collateral_signed<'a>(
+    program_id: &Pubkey,
+    collateral_amount: u64,
+    source_collateral: AccountInfo<'a>,
+    destination_collateral: AccountInfo<'a>,
+    withdraw_reserve: AccountInfo<'a>,
+    obligation: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    obligation_owner: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = WithdrawObligationCollateralAccounts {
+        source_collateral: *source_collateral.key,
+        destination_collateral: *destination_collateral.key,
+        withdraw_reserve: *withdraw_reserve.key,
+        obligation: *obligation.key,
+        lending_market: *lending_market.key,
+        lending_market_authority: *lending_market_authority.key,
+        obligation_owner: *obligation_owner.key,
+        token_program: *token_program.key,
+    };
+    invoke_lending(
+        program_id,
+        accounts.to_account_metas(),
+        LendingInstruction::WithdrawObligationCollateral { collateral_amount },
+        &[
+            source_collateral,
+            destination_collateral,
+            withdraw_reserve,
+            obligation,
+            lending_market,
+            lending_market_authority,
+            obligation_owner,
+            token_program,
+        ],
+        signers_seeds,
+    )
+}
+
+/// Invokes a `BorrowObligationLiquidity` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn borrow_obligation_liquidity<'a>(
+    program_id: &Pubkey,
+    liquidity_amount: u64,
+    source_liquidity: AccountInfo<'a>,
+    destination_liquidity: AccountInfo<'a>,
+    borrow_reserve: AccountInfo<'a>,
+    obligation: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    obligation_owner: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    borrow_reserve_liquidity_fee_receiver: AccountInfo<'a>,
+    larix_oracle_program: AccountInfo<'a>,
+    mine_mint: AccountInfo<'a>,
+) -> ProgramResult {
+    borrow_obligation_liquidity_signed(
+        program_id,
+        liquidity_amount,
+        source_liquidity,
+        destination_liquidity,
+        borrow_reserve,
+        obligation,
+        lending_market,
+        lending_market_authority,
+        obligation_owner,
+        token_program,
+        borrow_reserve_liquidity_fee_receiver,
+        larix_oracle_program,
+        mine_mint,
+        &[],
+    )
+}
+
+/// Invokes a `BorrowObligationLiquidity` instruction signed by `signers_seeds`
+#[allow(clippy::too_many_arguments)]
+pub fn borrow_obligation_liquidity_signed<'a>(
+    program_id: &Pubkey,
+    liquidity_amount: u64,
+    source_liquidity: AccountInfo<'a>,
+    destination_liquidity: AccountInfo<'a>,
+    borrow_reserve: AccountInfo<'a>,
+    obligation: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    obligation_owner: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    borrow_reserve_liquidity_fee_receiver: AccountInfo<'a>,
+    larix_oracle_program: AccountInfo<'a>,
+    mine_mint: AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = BorrowObligationLiquidityAccounts {
+        source_liquidity: *source_liquidity.key,
+        destination_liquidity: *destination_liquidity.key,
+        borrow_reserve: *borrow_reserve.key,
+        obligation: *obligation.key,
+        lending_market: *lending_market.key,
+        lending_market_authority: *lending_market_authority.key,
+        obligation_owner: *obligation_owner.key,
+        token_program: *token_program.key,
+        borrow_reserve_liquidity_fee_receiver: *borrow_reserve_liquidity_fee_receiver.key,
+        larix_oracle_program: *larix_oracle_program.key,
+        mine_mint: *mine_mint.key,
+    };
+    invoke_lending(
+        program_id,
+        accounts.to_account_metas(),
+        LendingInstruction::BorrowObligationLiquidity { liquidity_amount },
+        &[
+            source_liquidity,
+            destination_liquidity,
+            borrow_reserve,
+            obligation,
+            lending_market,
+            lending_market_authority,
+            obligation_owner,
+            token_program,
+            borrow_reserve_liquidity_fee_receiver,
+            larix_oracle_program,
+            mine_mint,
+        ],
+        signers_seeds,
+    )
+}
+
+/// Invokes a `RepayObligationLiquidity` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn repay_obligation_liquidity<'a>(
+    program_id: &Pubkey,
+    liquidity_amount: u64,
+    source_liquidity: AccountInfo<'a>,
+    destination_liquidity: AccountInfo<'a>,
+    repay_reserve: AccountInfo<'a>,
+    obligation: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    user_transfer_authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+) -> ProgramResult {
+    repay_obligation_liquidity_signed(
+        program_id,
+        liquidity_amount,
+        source_liquidity,
+        destination_liquidity,
+        repay_reserve,
+        obligation,
+        lending_market,
+        user_transfer_authority,
+        token_program,
+        &[],
+    )
+}
+
+/// Invokes a `RepayObligationLiquidity` instruction signed by `signers_seeds`
+#[allow(clippy::too_many_arguments)]
+pub fn repay_obligation_liquidity_signed<'a>(
+    program_id: &Pubkey,
+    liquidity_amount: u64,
+    source_liquidity: AccountInfo<'a>,
+    destination_liquidity: AccountInfo<'a>,
+    repay_reserve: AccountInfo<'a>,
+    obligation: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    user_transfer_authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = RepayObligationLiquidityAccounts {
+        source_liquidity: *source_liquidity.key,
+        destination_liquidity: *destination_liquidity.key,
+        repay_reserve: *repay_reserve.key,
+        obligation: *obligation.key,
+        lending_market: *lending_market.key,
+        user_transfer_authority: *user_transfer_authority.key,
+        token_program: *token_program.key,
+    };
+    invoke_lending(
+        program_id,
+        accounts.to_account_metas(),
+        LendingInstruction::RepayObligationLiquidity { liquidity_amount },
+        &[
+            source_liquidity,
+            destination_liquidity,
+            repay_reserve,
+            obligation,
+            lending_market,
+            user_transfer_authority,
+            token_program,
+        ],
+        signers_seeds,
+    )
+}
+
+/// Invokes a `ClaimMine` instruction, the obligation is only passed when the owner has one
+///
+/// `claim_times` and `claim_ratio` are checked against the lending market account first.
+#[allow(clippy::too_many_arguments)]
+pub fn claim_mine<'a>(
+    program_id: &Pubkey,
+    claim_times: u16,
+    claim_ratio: u16,
+    token_program: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    owner: AccountInfo<'a>,
+    mining: AccountInfo<'a>,
+    obligation: Option<AccountInfo<'a>>,
+    target: ClaimMineTargetInfos<'a>,
+) -> ProgramResult {
+    claim_mine_signed(
+        program_id,
+        claim_times,
+        claim_ratio,
+        token_program,
+        lending_market,
+        lending_market_authority,
+        owner,
+        mining,
+        obligation,
+        target,
+        &[],
+    )
+}
+
+/// Invokes a `ClaimMine` instruction signed by `signers_seeds`
+#[allow(clippy::too_many_arguments)]
+pub fn claim_mine_signed<'a>(
+    program_id: &Pubkey,
+    claim_times: u16,
+    claim_ratio: u16,
+    token_program: AccountInfo<'a>,
+    lending_market: AccountInfo<'a>,
+    lending_market_authority: AccountInfo<'a>,
+    owner: AccountInfo<'a>,
+    mining: AccountInfo<'a>,
+    obligation: Option<AccountInfo<'a>>,
+    target: ClaimMineTargetInfos<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    LendingMarket::unpack(&lending_market.try_borrow_data()?)?
+        .check_claim_mine(claim_times, claim_ratio)?;
+    let (target_accounts, target_infos) = match target {
+        ClaimMineTargetInfos::Destination {
+            destination,
+            source,
+        } => (
+            ClaimMineTargetAccounts::Destination {
+                destination: *destination.key,
+                source: *source.key,
+            },
+            vec![destination, source],
+        ),
+        ClaimMineTargetInfos::LockProgram {
+            lock_program,
+            lock_pool,
+            user_info,
+        } => (
+            ClaimMineTargetAccounts::LockProgram {
+                lock_program: *lock_program.key,
+                lock_pool: *lock_pool.key,
+                user_info: *user_info.key,
+            },
+            vec![lock_program, lock_pool, user_info],
+        ),
+    };
+    let accounts = ClaimMineAccounts {
+        token_program: *token_program.key,
+        lending_market: *lending_market.key,
+        lending_market_authority: *lending_market_authority.key,
+        owner: *owner.key,
+        mining: *mining.key,
+        obligation: obligation.as_ref().map(|obligation| *obligation.key),
+        target: target_accounts,
+    };
+    let mut account_infos = vec![
+        token_program,
+        lending_market,
+        lending_market_authority,
+        owner,
+        mining,
+    ];
+    account_infos.extend(obligation);
+    account_infos.extend(target_infos);
+    invoke_lending(
+        program_id,
+        accounts.to_account_metas(),
+        LendingInstruction::ClaimMine {
+            claim_times,
+            claim_ratio,
+        },
+        &account_infos,
+        signers_seeds,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{error::LendingError, instruction, state::PROGRAM_VERSION};
+    use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use std::sync::Mutex;
+
+    static INVOKED: Mutex<Vec<Instruction>> = Mutex::new(vec![]);
+
+    struct RecordingStubs;
+    impl SyscallStubs for RecordingStubs {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            _account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            INVOKED.lock().unwrap().push(instruction.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn invocations_match_builders() {
+        set_syscall_stubs(Box::new(RecordingStubs));
+        let program_id = crate::id();
+        let owner = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..11).map(|_| Pubkey::new_unique()).collect();
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![vec![]; keys.len()];
+        data[1] = vec![0; LendingMarket::LEN];
+        LendingMarket {
+            version: PROGRAM_VERSION,
+            max_claim_times: 200,
+            ..LendingMarket::default()
+        }
+        .pack_into_slice(&mut data[1]);
+        let infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &owner, false, 0)
+            })
+            .collect();
+
+        deposit_reserve_liquidity_signed(
+            &program_id,
+            10,
+            infos[0].clone(),
+            infos[1].clone(),
+            infos[2].clone(),
+            infos[3].clone(),
+            infos[4].clone(),
+            infos[5].clone(),
+            infos[6].clone(),
+            infos[7].clone(),
+            infos[8].clone(),
+            &[],
+        )
+        .unwrap();
+        let mut expected = instruction::deposit_reserve_liquidity(
+            program_id, 10, keys[0], keys[1], keys[2], keys[3], keys[4], keys[5], keys[6], keys[7],
+        );
+        expected.accounts[8].pubkey = keys[8];
+        assert_eq!(INVOKED.lock().unwrap().pop(), Some(expected));
+
+        refresh_obligation(&program_id, infos[0].clone(), &infos[1..4]).unwrap();
+        assert_eq!(
+            INVOKED.lock().unwrap().pop(),
+            Some(instruction::refresh_obligation(
+                program_id,
+                keys[0],
+                keys[1..4].to_vec()
+            ))
+        );
+
+        let claim = |claim_times| {
+            claim_mine(
+                &program_id,
+                claim_times,
+                5_000,
+                infos[0].clone(),
+                infos[1].clone(),
+                infos[2].clone(),
+                infos[3].clone(),
+                infos[4].clone(),
+                Some(infos[5].clone()),
+                ClaimMineTargetInfos::LockProgram {
+                    lock_program: infos[6].clone(),
+                    lock_pool: infos[7].clone(),
+                    user_info: infos[8].clone(),
+                },
+            )
+        };
+        assert_eq!(claim(201), Err(LendingError::InvalidAmount.into()));
+        assert_eq!(INVOKED.lock().unwrap().pop(), None);
+        claim(100).unwrap();
+        let invoked = INVOKED.lock().unwrap().pop().unwrap();
+        assert_eq!(
+            LendingInstruction::unpack(&invoked.data),
+            Ok(LendingInstruction::ClaimMine {
+                claim_times: 100,
+                claim_ratio: 5_000
+            })
+        );
+        let pubkeys: Vec<Pubkey> = invoked.accounts.iter().map(|a| a.pubkey).collect();
+        assert_eq!(pubkeys, keys[0..9].to_vec());
+        assert!(invoked.accounts[3].is_signer);
+        assert!(!invoked.accounts[6].is_writable);
+    }
+}
@@ -223,6 +223,9 @@ pub enum LendingError {
     // 70
     #[error("No This Attribute")]
     NoThisAttribute,
+    /// Instruction tag is not known to this version of the crate.
+    #[error("Unknown instruction tag")]
+    UnknownInstructionTag,
 
 }
 
@@ -3,15 +3,31 @@
 
 use std::mem::size_of;
 use crate::{
+    accounts::{
+        BorrowObligationLiquidityAccounts, ClaimMineAccounts, ClaimMineTargetAccounts,
+        ClaimMiningMineAccounts, ClaimObligationMineAccounts, ClaimOwnerFeeAccounts,
+        DepositMiningAccounts, DepositObligationCollateralAccounts,
+        DepositReserveLiquidityAccounts, FlashLoanAccounts, InitLendingMarketAccounts,
+        InitMiningAccounts, InitObligationAccounts, InitReserveAccounts,
+        LiquidateObligation2Accounts, ReceivePendingOwnerAccounts,
+        RedeemReserveCollateralAccounts, RedeemReserveCollateralDestination,
+        RefreshMiningAccounts, RefreshObligationAccounts, RefreshReservesAccounts,
+        RepayObligationLiquidityAccounts, SetConfigAccounts, SetLendingMarketOwnerAccounts,
+        WithdrawMiningAccounts, WithdrawObligationCollateralAccounts,
+    },
     error::LendingError,
+    state::{
+        lending_market::{find_lending_market_authority, LendingMarket},
+        reserve::{ReserveConfig, ReserveFees},
+    },
 };
 use solana_program::{
     msg,
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvar,
 };
 use solana_program::instruction::{AccountMeta, Instruction};
-use solana_program::pubkey::PUBKEY_BYTES;
 use crate::util::unpack_util::{unpack_bool, unpack_u16};
 use crate::util::unpack_util::{
     unpack_bytes32,
@@ -90,7 +106,10 @@ pub enum LendingInstruction {
 
     InitReserve {
         /// Reserve configuration values
+        config: ReserveConfig,
+        /// Amount of mine token for this reserve per slot
         total_mining_speed: u64,
+        /// Stored as the reserve's `Bonus::supply_rate`
         kink_util_rate: u64,
         use_pyth_oracle:bool,
         is_lp:bool,
@@ -335,9 +354,14 @@ pub enum LendingInstruction {
         call_back_data: Vec<u8>
     },
     // 14
+    /// Update a configuration value of a reserve or of the lending market.
     ///
-    ///
-    SetConfig ,
+    /// The payload and account layout are defined by the deployed program and are passed
+    /// through as is.
+    SetConfig {
+        /// Config update payload following the tag
+        config_data: Vec<u8>,
+    },
     // 16
     /// 0. `[]` Mining account
     /// 1. `[signer]` Mining owner
@@ -454,17 +478,17 @@ pub enum LendingInstruction {
     ///  1.  `[]` Lending market account
     ///  2.  `[]` Lending market authority
     ///  3.  `[Signer]` Owner
-    ///  4.  `[]` Mining account
-    ///  5.  `[]` Obligation account.
+    ///  4.  `[writable]` Mining account
+    ///  5.  `[writable]` Obligation account.
     ///           After accounts pop if this account can not provided
-    ///  6.  `[]` Destination account
-    ///  7.  `[]` Source account
+    ///  6.  `[writable]` Destination account
+    ///  7.  `[writable]` Source account
     ///
     ///  or
     ///
     ///  6.  `[]` Larix lock program
-    ///  7.  `[]` Larix lock pool
-    ///  8.  `[]` User larix info account
+    ///  7.  `[writable]` Larix lock pool
+    ///  8.  `[writable]` User larix info account
     ///
     ClaimMine{
         // claim times of user expected got: 100 equals 100%
@@ -474,14 +498,175 @@ pub enum LendingInstruction {
     }
 }
 
+/// First byte of a packed [LendingInstruction](enum.LendingInstruction.html)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum LendingInstructionTag {
+    /// Tag of `LendingInstruction::InitLendingMarket`
+    InitLendingMarket = 0,
+    /// Tag of `LendingInstruction::SetLendingMarketOwner`
+    SetLendingMarketOwner = 1,
+    /// Tag of `LendingInstruction::InitReserve`
+    InitReserve = 2,
+    /// Tag of `LendingInstruction::RefreshReserve`
+    RefreshReserve = 3,
+    /// Tag of `LendingInstruction::DepositReserveLiquidity`
+    DepositReserveLiquidity = 4,
+    /// Tag of `LendingInstruction::RedeemReserveCollateral`
+    RedeemReserveCollateral = 5,
+    /// Tag of `LendingInstruction::InitObligation`
+    InitObligation = 6,
+    /// Tag of `LendingInstruction::RefreshObligation`
+    RefreshObligation = 7,
+    /// Tag of `LendingInstruction::DepositObligationCollateral`
+    DepositObligationCollateral = 8,
+    /// Tag of `LendingInstruction::WithdrawObligationCollateral`
+    WithdrawObligationCollateral = 9,
+    /// Tag of `LendingInstruction::BorrowObligationLiquidity`
+    BorrowObligationLiquidity = 10,
+    /// Tag of `LendingInstruction::RepayObligationLiquidity`
+    RepayObligationLiquidity = 11,
+    /// Tag of `LendingInstruction::LiquidateObligation`
+    LiquidateObligation = 12,
+    /// Tag of `LendingInstruction::FlashLoan`
+    FlashLoan = 13,
+    /// Tag of `LendingInstruction::SetConfig`
+    SetConfig = 14,
+    // 15 is not assigned
+    /// Tag of `LendingInstruction::InitMining`
+    InitMining = 16,
+    /// Tag of `LendingInstruction::RefreshMining`
+    RefreshMining = 17,
+    /// Tag of `LendingInstruction::DepositMining`
+    DepositMining = 18,
+    /// Tag of `LendingInstruction::WithdrawMining`
+    WithdrawMining = 19,
+    /// Tag of `LendingInstruction::ClaimMiningMine`
+    ClaimMiningMine = 20,
+    /// Tag of `LendingInstruction::ClaimObligationMine`
+    ClaimObligationMine = 21,
+    /// Tag of `LendingInstruction::ClaimOwnerFee`
+    ClaimOwnerFee = 22,
+    /// Tag of `LendingInstruction::ReceivePendingOwner`
+    ReceivePendingOwner = 23,
+    /// Tag of `LendingInstruction::RefreshReserves`
+    RefreshReserves = 24,
+    /// Tag of `LendingInstruction::LiquidateObligation2`
+    LiquidateObligation2 = 25,
+    /// Tag of `LendingInstruction::ClaimMine`
+    ClaimMine = 26,
+}
+
+/// Error unpacking a [LendingInstruction](enum.LendingInstruction.html)
+#[derive(Clone, Debug, PartialEq)]
+pub enum UnpackError {
+    /// Instruction tag unknown to this crate, e.g. an instruction of a newer program version
+    UnknownTag(u8),
+    /// Missing tag or invalid data of a known instruction
+    InvalidData(ProgramError),
+}
+
+impl From<UnpackError> for ProgramError {
+    fn from(error: UnpackError) -> Self {
+        match error {
+            UnpackError::UnknownTag(_) => LendingError::UnknownInstructionTag.into(),
+            UnpackError::InvalidData(error) => error,
+        }
+    }
+}
+
+impl TryFrom<u8> for LendingInstructionTag {
+    type Error = UnpackError;
+
+    fn try_from(tag: u8) -> Result<Self, Self::Error> {
+        match tag {
+            0 => Ok(Self::InitLendingMarket),
+            1 => Ok(Self::SetLendingMarketOwner),
+            2 => Ok(Self::InitReserve),
+            3 => Ok(Self::RefreshReserve),
+            4 => Ok(Self::DepositReserveLiquidity),
+            5 => Ok(Self::RedeemReserveCollateral),
+            6 => Ok(Self::InitObligation),
+            7 => Ok(Self::RefreshObligation),
+            8 => Ok(Self::DepositObligationCollateral),
+            9 => Ok(Self::WithdrawObligationCollateral),
+            10 => Ok(Self::BorrowObligationLiquidity),
+            11 => Ok(Self::RepayObligationLiquidity),
+            12 => Ok(Self::LiquidateObligation),
+            13 => Ok(Self::FlashLoan),
+            14 => Ok(Self::SetConfig),
+            16 => Ok(Self::InitMining),
+            17 => Ok(Self::RefreshMining),
+            18 => Ok(Self::DepositMining),
+            19 => Ok(Self::WithdrawMining),
+            20 => Ok(Self::ClaimMiningMine),
+            21 => Ok(Self::ClaimObligationMine),
+            22 => Ok(Self::ClaimOwnerFee),
+            23 => Ok(Self::ReceivePendingOwner),
+            24 => Ok(Self::RefreshReserves),
+            25 => Ok(Self::LiquidateObligation2),
+            26 => Ok(Self::ClaimMine),
+            _ => Err(UnpackError::UnknownTag(tag)),
+        }
+    }
+}
+
 impl LendingInstruction {
+    /// Tag the instruction is packed with
+    pub fn tag(&self) -> LendingInstructionTag {
+        match self {
+            Self::InitLendingMarket { .. } => LendingInstructionTag::InitLendingMarket,
+            Self::SetLendingMarketOwner { .. } => LendingInstructionTag::SetLendingMarketOwner,
+            Self::InitReserve { .. } => LendingInstructionTag::InitReserve,
+            Self::RefreshReserve => LendingInstructionTag::RefreshReserve,
+            Self::DepositReserveLiquidity { .. } => LendingInstructionTag::DepositReserveLiquidity,
+            Self::RedeemReserveCollateral { .. } => LendingInstructionTag::RedeemReserveCollateral,
+            Self::InitObligation => LendingInstructionTag::InitObligation,
+            Self::RefreshObligation => LendingInstructionTag::RefreshObligation,
+            Self::DepositObligationCollateral { .. } => LendingInstructionTag::DepositObligationCollateral,
+            Self::WithdrawObligationCollateral { .. } => LendingInstructionTag::WithdrawObligationCollateral,
+            Self::BorrowObligationLiquidity { .. } => LendingInstructionTag::BorrowObligationLiquidity,
+            Self::RepayObligationLiquidity { .. } => LendingInstructionTag::RepayObligationLiquidity,
+            Self::LiquidateObligation { .. } => LendingInstructionTag::LiquidateObligation,
+            Self::FlashLoan { .. } => LendingInstructionTag::FlashLoan,
+            Self::SetConfig { .. } => LendingInstructionTag::SetConfig,
+            Self::InitMining => LendingInstructionTag::InitMining,
+            Self::RefreshMining => LendingInstructionTag::RefreshMining,
+            Self::DepositMining { .. } => LendingInstructionTag::DepositMining,
+            Self::WithdrawMining { .. } => LendingInstructionTag::WithdrawMining,
+            Self::ClaimMiningMine => LendingInstructionTag::ClaimMiningMine,
+            Self::ClaimObligationMine => LendingInstructionTag::ClaimObligationMine,
+            Self::ClaimOwnerFee => LendingInstructionTag::ClaimOwnerFee,
+            Self::ReceivePendingOwner => LendingInstructionTag::ReceivePendingOwner,
+            Self::RefreshReserves => LendingInstructionTag::RefreshReserves,
+            Self::LiquidateObligation2 { .. } => LendingInstructionTag::LiquidateObligation2,
+            Self::ClaimMine { .. } => LendingInstructionTag::ClaimMine,
+        }
+    }
+
     /// Unpacks a byte buffer into a [LendingInstruction](enum.LendingInstruction.html).
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_unpack(input).map_err(|error| {
+            if let UnpackError::UnknownTag(tag) = error {
+                msg!("Unknown instruction tag {}", tag);
+            }
+            error.into()
+        })
+    }
+
+    /// Unpacks a byte buffer into a [LendingInstruction](enum.LendingInstruction.html), keeping
+    /// the tag of instructions unknown to this crate.
+    pub fn try_unpack(input: &[u8]) -> Result<Self, UnpackError> {
         let (&tag, rest) = input
             .split_first()
-            .ok_or(LendingError::InstructionUnpackError)?;
+            .ok_or_else(|| UnpackError::InvalidData(LendingError::InstructionUnpackError.into()))?;
+        let tag = LendingInstructionTag::try_from(tag)?;
+        Self::unpack_data(tag, rest).map_err(UnpackError::InvalidData)
+    }
+
+    fn unpack_data(tag: LendingInstructionTag, rest: &[u8]) -> Result<Self, ProgramError> {
         Ok(match tag {
-            0 => {
+            LendingInstructionTag::InitLendingMarket => {
                 let (owner, rest) = unpack_pubkey(rest)?;
                 let (quote_currency, _rest) = unpack_bytes32(rest)?;
 
@@ -491,107 +676,124 @@ impl LendingInstruction {
                 }
 
             }
-            1 => {
+            LendingInstructionTag::SetLendingMarketOwner => {
                 let (new_owner, _rest) = unpack_pubkey(rest)?;
                 Self::SetLendingMarketOwner { new_owner }
             }
-            2 => {
-                let (_optimal_utilization_rate, rest) = unpack_u8(rest)?;
-                let (_loan_to_value_ratio, rest) = unpack_u8(rest)?;
-                let (_liquidation_bonus, rest) = unpack_u8(rest)?;
-                let (_liquidation_threshold, rest) = unpack_u8(rest)?;
-                let (_min_borrow_rate, rest) = unpack_u8(rest)?;
-                let (_optimal_borrow_rate, rest) = unpack_u8(rest)?;
-                let (_max_borrow_ratse, rest) = unpack_u8(rest)?;
-                let (_borrow_fee_wad, rest) = unpack_u64(rest)?;
-                let (_reserve_owner_fee_wad, rest) = unpack_u64(rest)?;
-                let (_flash_loan_fee_wad, rest) = unpack_u64(rest)?;
-                let (_host_fee_percentage, rest) = unpack_u8(rest)?;
+            LendingInstructionTag::InitReserve => {
+                let (optimal_utilization_rate, rest) = unpack_u8(rest)?;
+                let (loan_to_value_ratio, rest) = unpack_u8(rest)?;
+                let (liquidation_bonus, rest) = unpack_u8(rest)?;
+                let (liquidation_threshold, rest) = unpack_u8(rest)?;
+                let (min_borrow_rate, rest) = unpack_u8(rest)?;
+                let (optimal_borrow_rate, rest) = unpack_u8(rest)?;
+                let (max_borrow_rate, rest) = unpack_u8(rest)?;
+                let (borrow_fee_wad, rest) = unpack_u64(rest)?;
+                let (reserve_owner_fee_wad, rest) = unpack_u64(rest)?;
+                let (flash_loan_fee_wad, rest) = unpack_u64(rest)?;
+                let (host_fee_percentage, rest) = unpack_u8(rest)?;
                 let (total_mining_speed,rest) = unpack_u64(rest)?;
                 let (kink_util_rate,rest) = unpack_u64(rest)?;
                 let (use_pyth_oracle,rest) = unpack_bool(rest)?;
                 let (is_lp,_rest) = unpack_bool(rest)?;
                 Self::InitReserve {
+                    config: ReserveConfig {
+                        optimal_utilization_rate,
+                        loan_to_value_ratio,
+                        liquidation_bonus,
+                        liquidation_threshold,
+                        min_borrow_rate,
+                        optimal_borrow_rate,
+                        max_borrow_rate,
+                        fees: ReserveFees {
+                            borrow_fee_wad,
+                            reserve_owner_fee_wad,
+                            flash_loan_fee_wad,
+                            host_fee_percentage,
+                            host_fee_receivers: vec![],
+                        },
+                        ..ReserveConfig::default()
+                    },
                     total_mining_speed,
                     kink_util_rate,
                     use_pyth_oracle,
                     is_lp
                 }
             }
-            3 => Self::RefreshReserve,
-            4 => {
+            LendingInstructionTag::RefreshReserve => Self::RefreshReserve,
+            LendingInstructionTag::DepositReserveLiquidity => {
                 let (liquidity_amount, _rest) = unpack_u64(rest)?;
                 Self::DepositReserveLiquidity { liquidity_amount }
             }
-            5 => {
+            LendingInstructionTag::RedeemReserveCollateral => {
                 let (collateral_amount, _rest) = unpack_u64(rest)?;
                 Self::RedeemReserveCollateral { collateral_amount }
             }
-            6 => Self::InitObligation,
-            7 => Self::RefreshObligation,
-            8 => {
+            LendingInstructionTag::InitObligation => Self::InitObligation,
+            LendingInstructionTag::RefreshObligation => Self::RefreshObligation,
+            LendingInstructionTag::DepositObligationCollateral => {
                 let (collateral_amount, _rest) = unpack_u64(rest)?;
                 Self::DepositObligationCollateral { collateral_amount }
             }
-            9 => {
+            LendingInstructionTag::WithdrawObligationCollateral => {
                 let (collateral_amount, _rest) = unpack_u64(rest)?;
                 Self::WithdrawObligationCollateral { collateral_amount }
             }
-            10 => {
+            LendingInstructionTag::BorrowObligationLiquidity => {
                 let (liquidity_amount, _rest) = unpack_u64(rest)?;
                 Self::BorrowObligationLiquidity { liquidity_amount }
             }
-            11 => {
+            LendingInstructionTag::RepayObligationLiquidity => {
                 let (liquidity_amount, _rest) = unpack_u64(rest)?;
                 Self::RepayObligationLiquidity { liquidity_amount }
             }
-            12 => {
+            LendingInstructionTag::LiquidateObligation => {
                 let (liquidity_amount, _rest) = unpack_u64(rest)?;
                 Self::LiquidateObligation { liquidity_amount }
             }
-            13 => {
+            LendingInstructionTag::FlashLoan => {
                 let (amount, rest) = unpack_u64(rest)?;
                 let mut call_back_data =  Vec::with_capacity(rest.len());
                 call_back_data.extend_from_slice(rest);
                 Self::FlashLoan { amount ,call_back_data}
             }
-            14 => {
-                Self::SetConfig
+            LendingInstructionTag::SetConfig => {
+                Self::SetConfig { config_data: rest.to_vec() }
             }
-            16 => {
+            LendingInstructionTag::InitMining => {
                 Self::InitMining
             }
-            17 => {
+            LendingInstructionTag::RefreshMining => {
                 Self::RefreshMining
             }
-            18 => {
+            LendingInstructionTag::DepositMining => {
                 let (amount, _rest) = unpack_u64(rest)?;
                 Self::DepositMining { amount }
             }
-            19 => {
+            LendingInstructionTag::WithdrawMining => {
                 let (amount, _rest) = unpack_u64(rest)?;
                 Self::WithdrawMining { amount }
             }
-            20 => {
+            LendingInstructionTag::ClaimMiningMine => {
                 Self::ClaimMiningMine
             }
-            21 => {
+            LendingInstructionTag::ClaimObligationMine => {
                 Self::ClaimObligationMine
             }
-            22 => {
+            LendingInstructionTag::ClaimOwnerFee => {
                 Self::ClaimOwnerFee
             }
-            23 => {
+            LendingInstructionTag::ReceivePendingOwner => {
                 Self::ReceivePendingOwner
             }
-            24 => {
+            LendingInstructionTag::RefreshReserves => {
                 Self::RefreshReserves
             }
-            25 => {
+            LendingInstructionTag::LiquidateObligation2 => {
                 let (liquidity_amount, _rest) = unpack_u64(rest)?;
                 Self::LiquidateObligation2 {liquidity_amount}
             }
-            26 => {
+            LendingInstructionTag::ClaimMine => {
                 let (subsidy_times, rest) = unpack_u16(rest)?;
                 let (claim_ratio, _rest) = unpack_u16(rest)?;
                 Self::ClaimMine {
@@ -599,98 +801,270 @@ impl LendingInstruction {
                     claim_ratio
                 }
             }
-            _ => {
-                msg!("Instruction cannot be unpacked");
-                return Err(LendingError::InstructionUnpackError.into());
-            }
         })
     }
 
+    /// Packs a [LendingInstruction](enum.LendingInstruction.html) into a byte buffer.
     pub fn pack(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(size_of::<Self>());
-        match *self {
-            Self::RefreshReserves => {
-                buf.push(24)
+        buf.push(self.tag() as u8);
+        match self {
+            Self::InitLendingMarket { owner, quote_currency } => {
+                buf.extend_from_slice(owner.as_ref());
+                buf.extend_from_slice(quote_currency.as_ref());
+            }
+            Self::SetLendingMarketOwner { new_owner } => {
+                buf.extend_from_slice(new_owner.as_ref());
+            }
+            Self::InitReserve {
+                config,
+                total_mining_speed,
+                kink_util_rate,
+                use_pyth_oracle,
+                is_lp,
+            } => {
+                buf.extend_from_slice(&config.optimal_utilization_rate.to_le_bytes());
+                buf.extend_from_slice(&config.loan_to_value_ratio.to_le_bytes());
+                buf.extend_from_slice(&config.liquidation_bonus.to_le_bytes());
+                buf.extend_from_slice(&config.liquidation_threshold.to_le_bytes());
+                buf.extend_from_slice(&config.min_borrow_rate.to_le_bytes());
+                buf.extend_from_slice(&config.optimal_borrow_rate.to_le_bytes());
+                buf.extend_from_slice(&config.max_borrow_rate.to_le_bytes());
+                buf.extend_from_slice(&config.fees.borrow_fee_wad.to_le_bytes());
+                buf.extend_from_slice(&config.fees.reserve_owner_fee_wad.to_le_bytes());
+                buf.extend_from_slice(&config.fees.flash_loan_fee_wad.to_le_bytes());
+                buf.extend_from_slice(&config.fees.host_fee_percentage.to_le_bytes());
+                buf.extend_from_slice(&total_mining_speed.to_le_bytes());
+                buf.extend_from_slice(&kink_util_rate.to_le_bytes());
+                buf.push(*use_pyth_oracle as u8);
+                buf.push(*is_lp as u8);
             }
             Self::DepositReserveLiquidity { liquidity_amount } => {
-                buf.push(4);
                 buf.extend_from_slice(&liquidity_amount.to_le_bytes());
             }
             Self::RedeemReserveCollateral { collateral_amount } => {
-                buf.push(5);
                 buf.extend_from_slice(&collateral_amount.to_le_bytes());
             }
-            Self::InitObligation => {
-                buf.push(6);
-            }
-            Self::RefreshObligation => {
-                buf.push(7);
-            }
             Self::DepositObligationCollateral { collateral_amount } => {
-                buf.push(8);
                 buf.extend_from_slice(&collateral_amount.to_le_bytes());
             }
             Self::WithdrawObligationCollateral { collateral_amount } => {
-                buf.push(9);
                 buf.extend_from_slice(&collateral_amount.to_le_bytes());
             }
             Self::BorrowObligationLiquidity { liquidity_amount } => {
-                buf.push(10);
                 buf.extend_from_slice(&liquidity_amount.to_le_bytes());
             }
             Self::RepayObligationLiquidity { liquidity_amount } => {
-                buf.push(11);
                 buf.extend_from_slice(&liquidity_amount.to_le_bytes());
             }
-            Self::InitMining => {
-                buf.push(16);
+            Self::LiquidateObligation { liquidity_amount } => {
+                buf.extend_from_slice(&liquidity_amount.to_le_bytes());
             }
-            Self::RefreshMining => {
-                buf.push(17);
+            Self::FlashLoan { amount, call_back_data } => {
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(call_back_data);
+            }
+            Self::SetConfig { config_data } => {
+                buf.extend_from_slice(config_data);
             }
             Self::DepositMining { amount } => {
-                buf.push(18);
                 buf.extend_from_slice(&amount.to_le_bytes());
             }
             Self::WithdrawMining { amount } => {
-                buf.push(19);
                 buf.extend_from_slice(&amount.to_le_bytes());
             }
-            Self::ClaimMiningMine => {
-                buf.push(20);
-            }
-            Self::ClaimObligationMine => {
-                buf.push(21);
-            }
             Self::LiquidateObligation2{liquidity_amount} =>{
-                buf.push(25);
                 buf.extend_from_slice(&liquidity_amount.to_le_bytes());
             }
             Self::ClaimMine { claim_times, claim_ratio} => {
-                buf.push(26);
                 buf.extend_from_slice(&claim_times.to_le_bytes());
                 buf.extend_from_slice(&claim_ratio.to_le_bytes());
             }
-            _ => {
-                // TODO: implementation
-            }
+            Self::RefreshReserve
+            | Self::RefreshReserves
+            | Self::InitObligation
+            | Self::RefreshObligation
+            | Self::InitMining
+            | Self::RefreshMining
+            | Self::ClaimMiningMine
+            | Self::ClaimObligationMine
+            | Self::ClaimOwnerFee
+            | Self::ReceivePendingOwner => {}
         }
         buf
     }
 }
+/// Creates an 'InitLendingMarket' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn init_lending_market(
+    program_id: Pubkey,
+    owner: Pubkey,
+    quote_currency: [u8; 32],
+    init_lending_market_authority_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    oracle_program_id: Pubkey,
+    larix_oracle_program_id: Pubkey,
+    larix_oracle_id: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: InitLendingMarketAccounts {
+            init_lending_market_authority: init_lending_market_authority_pubkey,
+            lending_market: lending_market_pubkey,
+            rent_sysvar: sysvar::rent::id(),
+            token_program: spl_token::id(),
+            oracle_program: oracle_program_id,
+            larix_oracle_program: larix_oracle_program_id,
+            larix_oracle: larix_oracle_id,
+        }
+        .to_account_metas(),
+        data: LendingInstruction::InitLendingMarket {
+            owner,
+            quote_currency,
+        }
+        .pack(),
+    }
+}
+/// Creates a 'SetLendingMarketOwner' instruction.
+pub fn set_lending_market_owner(
+    program_id: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner: Pubkey,
+    new_owner: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: SetLendingMarketOwnerAccounts {
+            lending_market: lending_market_pubkey,
+            lending_market_owner,
+        }
+        .to_account_metas(),
+        data: LendingInstruction::SetLendingMarketOwner { new_owner }.pack(),
+    }
+}
+/// Creates a 'ReceivePendingOwner' instruction.
+pub fn receive_pending_owner(
+    program_id: Pubkey,
+    lending_market_pubkey: Pubkey,
+    pending_owner: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: ReceivePendingOwnerAccounts {
+            lending_market: lending_market_pubkey,
+            pending_owner,
+        }
+        .to_account_metas(),
+        data: LendingInstruction::ReceivePendingOwner.pack(),
+    }
+}
+/// Creates an 'InitReserve' instruction.
+///
+/// The program stores `kink_util_rate` as the reserve's `Bonus::supply_rate`. The token accounts
+/// the program initializes are passed writable.
+#[allow(clippy::too_many_arguments)]
+pub fn init_reserve(
+    program_id: Pubkey,
+    config: ReserveConfig,
+    total_mining_speed: u64,
+    kink_util_rate: u64,
+    use_pyth_oracle: bool,
+    is_lp: bool,
+    reserve_pubkey: Pubkey,
+    reserve_liquidity_mint_pubkey: Pubkey,
+    reserve_liquidity_supply_pubkey: Pubkey,
+    reserve_liquidity_fee_receiver_pubkey: Pubkey,
+    pyth_product_pubkey: Pubkey,
+    params_1_pubkey: Pubkey,
+    params_2_pubkey: Pubkey,
+    reserve_collateral_mint_pubkey: Pubkey,
+    reserve_collateral_supply_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+    un_coll_supply_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: InitReserveAccounts {
+            reserve: reserve_pubkey,
+            liquidity_mint: reserve_liquidity_mint_pubkey,
+            liquidity_supply: reserve_liquidity_supply_pubkey,
+            liquidity_fee_receiver: reserve_liquidity_fee_receiver_pubkey,
+            pyth_product: pyth_product_pubkey,
+            params_1: params_1_pubkey,
+            params_2: params_2_pubkey,
+            collateral_mint: reserve_collateral_mint_pubkey,
+            collateral_supply: reserve_collateral_supply_pubkey,
+            lending_market: lending_market_pubkey,
+            lending_market_owner: lending_market_owner_pubkey,
+            un_coll_supply: un_coll_supply_pubkey,
+            clock_sysvar: sysvar::clock::id(),
+            rent_sysvar: sysvar::rent::id(),
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(),
+        data: LendingInstruction::InitReserve {
+            config,
+            total_mining_speed,
+            kink_util_rate,
+            use_pyth_oracle,
+            is_lp,
+        }
+        .pack(),
+    }
+}
+/// Creates a 'SetConfig' instruction.
+///
+/// `config_data` and `accounts` are sent as given, in the layout of the deployed program.
+pub fn set_config(
+    program_id: Pubkey,
+    config_data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: SetConfigAccounts { accounts }.to_account_metas(),
+        data: LendingInstruction::SetConfig { config_data }.pack(),
+    }
+}
+/// Creates a 'ClaimOwnerFee' instruction.
+pub fn claim_owner_fee(
+    program_id: Pubkey,
+    reserve_liquidity_supply_pubkey: Pubkey,
+    destination_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_owner_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: ClaimOwnerFeeAccounts {
+            reserve_liquidity_supply: reserve_liquidity_supply_pubkey,
+            destination: destination_pubkey,
+            lending_market: lending_market_pubkey,
+            lending_market_owner: lending_market_owner_pubkey,
+        }
+        .to_account_metas(),
+        data: LendingInstruction::ClaimOwnerFee.pack(),
+    }
+}
+/// Creates a 'RefreshReserves' instruction.
+///
+/// `oracles` holds the oracle account of each of `reserves`, the lists must have the same length.
+/// `RefreshReservesAccounts` takes the reserve and oracle pairs directly.
 pub fn refresh_reserves(
     program_id:Pubkey,
     reserves:Vec<Pubkey>,
     oracles:Vec<Pubkey>
 ) -> Instruction {
-    let mut accounts = Vec::with_capacity(reserves.len()*2);
-    for i in 0..reserves.len(){
-        accounts.push(AccountMeta::new(reserves[i],false));
-        accounts.push(AccountMeta::new_readonly(oracles[i],false));
-    }
+    assert_eq!(
+        reserves.len(),
+        oracles.len(),
+        "Each reserve must have an oracle account"
+    );
+    let reserves = reserves.into_iter().zip(oracles).collect();
     Instruction {
         program_id,
-        accounts,
+        accounts: RefreshReservesAccounts { reserves }.to_account_metas(),
         data: LendingInstruction::RefreshReserves.pack(),
     }
 }
@@ -709,17 +1083,18 @@ pub fn deposit_reserve_liquidity(
 ) -> Instruction {
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(source_liquidity_pubkey, false),
-            AccountMeta::new(destination_collateral_pubkey, false),
-            AccountMeta::new(reserve_pubkey, false),
-            AccountMeta::new(reserve_collateral_mint_pubkey, false),
-            AccountMeta::new(reserve_liquidity_supply_pubkey, false),
-            AccountMeta::new_readonly(lending_market_pubkey, false),
-            AccountMeta::new_readonly(lending_market_authority_pubkey, false),
-            AccountMeta::new_readonly(user_transfer_authority_pubkey, true),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts: DepositReserveLiquidityAccounts {
+            source_liquidity: source_liquidity_pubkey,
+            destination_collateral: destination_collateral_pubkey,
+            reserve: reserve_pubkey,
+            reserve_collateral_mint: reserve_collateral_mint_pubkey,
+            reserve_liquidity_supply: reserve_liquidity_supply_pubkey,
+            lending_market: lending_market_pubkey,
+            lending_market_authority: lending_market_authority_pubkey,
+            user_transfer_authority: user_transfer_authority_pubkey,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(),
         data: LendingInstruction::DepositReserveLiquidity { liquidity_amount }.pack(),
     }
 }
@@ -739,17 +1114,20 @@ pub fn redeem_reserve_collateral(
 ) -> Instruction {
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(source_collateral_pubkey, false),
-            AccountMeta::new(reserve_pubkey, false),
-            AccountMeta::new(reserve_collateral_mint_pubkey, false),
-            AccountMeta::new(reserve_liquidity_supply_pubkey, false),
-            AccountMeta::new_readonly(lending_market_pubkey, false),
-            AccountMeta::new_readonly(lending_market_authority_pubkey, false),
-            AccountMeta::new_readonly(user_transfer_authority_pubkey, true),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new(destination_liquidity_pubkey, false),
-        ],
+        accounts: RedeemReserveCollateralAccounts {
+            source_collateral: source_collateral_pubkey,
+            reserve: reserve_pubkey,
+            reserve_collateral_mint: reserve_collateral_mint_pubkey,
+            reserve_liquidity_supply: reserve_liquidity_supply_pubkey,
+            lending_market: lending_market_pubkey,
+            lending_market_authority: lending_market_authority_pubkey,
+            user_transfer_authority: user_transfer_authority_pubkey,
+            token_program: spl_token::id(),
+            destination: RedeemReserveCollateralDestination::Liquidity(
+                destination_liquidity_pubkey,
+            ),
+        }
+        .to_account_metas(),
         data: LendingInstruction::RedeemReserveCollateral { collateral_amount }.pack(),
     }
 }
@@ -763,12 +1141,13 @@ pub fn init_obligation(
 ) -> Instruction {
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(obligation_pubkey, false),
-            AccountMeta::new_readonly(lending_market_pubkey, false),
-            AccountMeta::new_readonly(obligation_owner_pubkey, true),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts: InitObligationAccounts {
+            obligation: obligation_pubkey,
+            lending_market: lending_market_pubkey,
+            obligation_owner: obligation_owner_pubkey,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(),
         data: LendingInstruction::InitObligation.pack(),
     }
 }
@@ -780,17 +1159,13 @@ pub fn refresh_obligation(
     obligation_pubkey: Pubkey,
     reserve_pubkeys: Vec<Pubkey>,
 ) -> Instruction {
-    let mut accounts = vec![
-        AccountMeta::new(obligation_pubkey, false)
-    ];
-    accounts.extend(
-        reserve_pubkeys
-            .into_iter()
-            .map(|pubkey| AccountMeta::new_readonly(pubkey, false)),
-    );
     Instruction {
         program_id,
-        accounts,
+        accounts: RefreshObligationAccounts {
+            obligation: obligation_pubkey,
+            reserves: reserve_pubkeys,
+        }
+        .to_account_metas(),
         data: LendingInstruction::RefreshObligation.pack(),
     }
 }
@@ -810,25 +1185,21 @@ pub fn deposit_obligation_collateral(
     user_transfer_authority_pubkey: Pubkey,
     reserve_pubkeys: Vec<Pubkey>,
 ) -> Instruction {
-    let mut accounts = vec![
-        AccountMeta::new(source_collateral_pubkey, false),
-        AccountMeta::new(destination_collateral_pubkey, false),
-        AccountMeta::new_readonly(deposit_reserve_pubkey, false),
-        AccountMeta::new(obligation_pubkey, false),
-        AccountMeta::new_readonly(lending_market_pubkey, false),
-        AccountMeta::new_readonly(lending_market_authority_pubkey, false),
-        AccountMeta::new_readonly(obligation_owner_pubkey, true),
-        AccountMeta::new_readonly(user_transfer_authority_pubkey, true),
-        AccountMeta::new_readonly(spl_token::id(), false),
-    ];
-    accounts.extend(
-        reserve_pubkeys
-            .into_iter()
-            .map(|pubkey| AccountMeta::new_readonly(pubkey, false)),
-    );
     Instruction {
         program_id,
-        accounts,
+        accounts: DepositObligationCollateralAccounts {
+            source_collateral: source_collateral_pubkey,
+            destination_collateral: destination_collateral_pubkey,
+            deposit_reserve: deposit_reserve_pubkey,
+            obligation: obligation_pubkey,
+            lending_market: lending_market_pubkey,
+            lending_market_authority: lending_market_authority_pubkey,
+            obligation_owner: obligation_owner_pubkey,
+            user_transfer_authority: user_transfer_authority_pubkey,
+            token_program: spl_token::id(),
+            reserves: reserve_pubkeys,
+        }
+        .to_account_metas(),
         data: LendingInstruction::DepositObligationCollateral { collateral_amount }.pack(),
     }
 }
@@ -844,22 +1215,21 @@ pub fn withdraw_obligation_collateral(
     lending_market_pubkey: Pubkey,
     obligation_owner_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority(&program_id, &lending_market_pubkey);
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(source_collateral_pubkey, false),
-            AccountMeta::new(destination_collateral_pubkey, false),
-            AccountMeta::new_readonly(withdraw_reserve_pubkey, false),
-            AccountMeta::new(obligation_pubkey, false),
-            AccountMeta::new_readonly(lending_market_pubkey, false),
-            AccountMeta::new_readonly(lending_market_authority_pubkey, false),
-            AccountMeta::new_readonly(obligation_owner_pubkey, true),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts: WithdrawObligationCollateralAccounts {
+            source_collateral: source_collateral_pubkey,
+            destination_collateral: destination_collateral_pubkey,
+            withdraw_reserve: withdraw_reserve_pubkey,
+            obligation: obligation_pubkey,
+            lending_market: lending_market_pubkey,
+            lending_market_authority: lending_market_authority_pubkey,
+            obligation_owner: obligation_owner_pubkey,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(),
         data: LendingInstruction::WithdrawObligationCollateral { collateral_amount }.pack(),
     }
 }
@@ -879,24 +1249,22 @@ pub fn borrow_obligation_liquidity(
     larix_oracle_program_pubkey:Pubkey,
     mine_mint_pubkey:Pubkey
 ) -> Instruction {
-    let accounts = vec![
-        AccountMeta::new(source_liquidity_pubkey, false),
-        AccountMeta::new(destination_liquidity_pubkey, false),
-        AccountMeta::new(borrow_reserve_pubkey, false),
-
-        AccountMeta::new(obligation_pubkey, false),
-        AccountMeta::new_readonly(lending_market_pubkey, false),
-        AccountMeta::new_readonly(lending_market_authority_pubkey, false),
-
-        AccountMeta::new_readonly(obligation_owner_pubkey, true),
-        AccountMeta::new_readonly(spl_token::id(), false),
-        AccountMeta::new(borrow_reserve_liquidity_fee_receiver_pubkey, false),
-        AccountMeta::new_readonly(larix_oracle_program_pubkey, false),
-        AccountMeta::new_readonly(mine_mint_pubkey, false),
-    ];
     Instruction {
         program_id,
-        accounts,
+        accounts: BorrowObligationLiquidityAccounts {
+            source_liquidity: source_liquidity_pubkey,
+            destination_liquidity: destination_liquidity_pubkey,
+            borrow_reserve: borrow_reserve_pubkey,
+            obligation: obligation_pubkey,
+            lending_market: lending_market_pubkey,
+            lending_market_authority: lending_market_authority_pubkey,
+            obligation_owner: obligation_owner_pubkey,
+            token_program: spl_token::id(),
+            borrow_reserve_liquidity_fee_receiver: borrow_reserve_liquidity_fee_receiver_pubkey,
+            larix_oracle_program: larix_oracle_program_pubkey,
+            mine_mint: mine_mint_pubkey,
+        }
+        .to_account_metas(),
         data: LendingInstruction::BorrowObligationLiquidity { liquidity_amount }.pack(),
     }
 }
@@ -914,18 +1282,288 @@ pub fn repay_obligation_liquidity(
 ) -> Instruction {
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(source_liquidity_pubkey, false),
-            AccountMeta::new(destination_liquidity_pubkey, false),
-            AccountMeta::new(repay_reserve_pubkey, false),
-            AccountMeta::new(obligation_pubkey, false),
-            AccountMeta::new_readonly(lending_market_pubkey, false),
-            AccountMeta::new_readonly(user_transfer_authority_pubkey, true),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts: RepayObligationLiquidityAccounts {
+            source_liquidity: source_liquidity_pubkey,
+            destination_liquidity: destination_liquidity_pubkey,
+            repay_reserve: repay_reserve_pubkey,
+            obligation: obligation_pubkey,
+            lending_market: lending_market_pubkey,
+            user_transfer_authority: user_transfer_authority_pubkey,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(),
         data: LendingInstruction::RepayObligationLiquidity { liquidity_amount }.pack(),
     }
 }
+/// Creates a `LiquidateObligation2` instruction
+///
+/// `liquidity_amount` can be `u64::MAX` to repay up to the close factor of the borrow.
+#[allow(clippy::too_many_arguments)]
+pub fn liquidate_obligation2(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    source_liquidity_pubkey: Pubkey,
+    destination_collateral_pubkey: Pubkey,
+    repay_reserve_pubkey: Pubkey,
+    repay_reserve_liquidity_supply_pubkey: Pubkey,
+    withdraw_reserve_pubkey: Pubkey,
+    withdraw_reserve_collateral_supply_pubkey: Pubkey,
+    obligation_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_authority_pubkey: Pubkey,
+    user_transfer_authority_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: LiquidateObligation2Accounts {
+            source_liquidity: source_liquidity_pubkey,
+            destination_collateral: destination_collateral_pubkey,
+            repay_reserve: repay_reserve_pubkey,
+            repay_reserve_liquidity_supply: repay_reserve_liquidity_supply_pubkey,
+            withdraw_reserve: withdraw_reserve_pubkey,
+            withdraw_reserve_collateral_supply: withdraw_reserve_collateral_supply_pubkey,
+            obligation: obligation_pubkey,
+            lending_market: lending_market_pubkey,
+            lending_market_authority: lending_market_authority_pubkey,
+            user_transfer_authority: user_transfer_authority_pubkey,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(),
+        data: LendingInstruction::LiquidateObligation2 { liquidity_amount }.pack(),
+    }
+}
+/// Creates an `InitMining` instruction
+pub fn init_mining(
+    program_id: Pubkey,
+    mining_pubkey: Pubkey,
+    mining_owner_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: InitMiningAccounts {
+            mining: mining_pubkey,
+            mining_owner: mining_owner_pubkey,
+            lending_market: lending_market_pubkey,
+        }
+        .to_account_metas(),
+        data: LendingInstruction::InitMining.pack(),
+    }
+}
+/// Creates a `RefreshMining` instruction
+pub fn refresh_mining(
+    program_id: Pubkey,
+    mining_pubkey: Pubkey,
+    reserve_pubkeys: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: RefreshMiningAccounts {
+            mining: mining_pubkey,
+            reserves: reserve_pubkeys,
+        }
+        .to_account_metas(),
+        data: LendingInstruction::RefreshMining.pack(),
+    }
+}
+/// Creates a `DepositMining` instruction
+///
+/// Stakes un-collateralized LTokens into the reserve's `Bonus::un_coll_supply_account`.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_mining(
+    program_id: Pubkey,
+    amount: u64,
+    source_collateral_pubkey: Pubkey,
+    un_coll_supply_pubkey: Pubkey,
+    mining_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    mining_owner_pubkey: Pubkey,
+    user_transfer_authority_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: DepositMiningAccounts {
+            source_collateral: source_collateral_pubkey,
+            un_coll_supply: un_coll_supply_pubkey,
+            mining: mining_pubkey,
+            reserve: reserve_pubkey,
+            lending_market: lending_market_pubkey,
+            mining_owner: mining_owner_pubkey,
+            user_transfer_authority: user_transfer_authority_pubkey,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(),
+        data: LendingInstruction::DepositMining { amount }.pack(),
+    }
+}
+/// Creates a `WithdrawMining` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_mining(
+    program_id: Pubkey,
+    amount: u64,
+    destination_collateral_pubkey: Pubkey,
+    un_coll_supply_pubkey: Pubkey,
+    mining_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_authority_pubkey: Pubkey,
+    mining_owner_pubkey: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: WithdrawMiningAccounts {
+            destination_collateral: destination_collateral_pubkey,
+            un_coll_supply: un_coll_supply_pubkey,
+            mining: mining_pubkey,
+            reserve: reserve_pubkey,
+            lending_market: lending_market_pubkey,
+            lending_market_authority: lending_market_authority_pubkey,
+            mining_owner: mining_owner_pubkey,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(),
+        data: LendingInstruction::WithdrawMining { amount }.pack(),
+    }
+}
+/// Creates a `ClaimMiningMine` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn claim_mining_mine(
+    program_id: Pubkey,
+    mining_pubkey: Pubkey,
+    mine_supply_pubkey: Pubkey,
+    destination_account_pubkey: Pubkey,
+    mining_owner_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_authority_pubkey: Pubkey,
+    reserve_pubkeys: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: ClaimMiningMineAccounts {
+            mining: mining_pubkey,
+            mine_supply: mine_supply_pubkey,
+            destination: destination_account_pubkey,
+            mining_owner: mining_owner_pubkey,
+            lending_market: lending_market_pubkey,
+            lending_market_authority: lending_market_authority_pubkey,
+            token_program: spl_token::id(),
+            reserves: reserve_pubkeys,
+        }
+        .to_account_metas(),
+        data: LendingInstruction::ClaimMiningMine.pack(),
+    }
+}
+/// Where the mine claimed by a `ClaimMine` instruction is paid to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClaimMineTarget {
+    /// Transfer the mine from the lending market mine supply to a token account
+    Destination {
+        /// Destination mine token account
+        destination_pubkey: Pubkey,
+    },
+    /// Lock the mine through the lending market's `mine_lock_program`
+    LockProgram {
+        /// Larix lock pool
+        lock_pool_pubkey: Pubkey,
+        /// User larix info account of the lock program
+        user_info_pubkey: Pubkey,
+    },
+}
+/// Creates a `ClaimMine` instruction
+///
+/// `claim_times` must be between 100 and `LendingMarket::max_claim_times`, `claim_ratio` is
+/// scaled by 10000. The obligation account is only passed when the owner has one.
+#[allow(clippy::too_many_arguments)]
+pub fn claim_mine(
+    program_id: Pubkey,
+    claim_times: u16,
+    claim_ratio: u16,
+    lending_market_pubkey: Pubkey,
+    lending_market: &LendingMarket,
+    lending_market_authority_pubkey: Pubkey,
+    owner_pubkey: Pubkey,
+    mining_pubkey: Pubkey,
+    obligation_pubkey: Option<Pubkey>,
+    target: ClaimMineTarget,
+) -> Result<Instruction, ProgramError> {
+    lending_market.check_claim_mine(claim_times, claim_ratio)?;
+    let target = match target {
+        ClaimMineTarget::Destination { destination_pubkey } => ClaimMineTargetAccounts::Destination {
+            destination: destination_pubkey,
+            source: lending_market.mine_supply_account,
+        },
+        ClaimMineTarget::LockProgram {
+            lock_pool_pubkey,
+            user_info_pubkey,
+        } => ClaimMineTargetAccounts::LockProgram {
+            lock_program: lending_market.mine_lock_program,
+            lock_pool: lock_pool_pubkey,
+            user_info: user_info_pubkey,
+        },
+    };
+    let accounts = ClaimMineAccounts {
+        token_program: spl_token::id(),
+        lending_market: lending_market_pubkey,
+        lending_market_authority: lending_market_authority_pubkey,
+        owner: owner_pubkey,
+        mining: mining_pubkey,
+        obligation: obligation_pubkey,
+        target,
+    }
+    .to_account_metas();
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: LendingInstruction::ClaimMine {
+            claim_times,
+            claim_ratio,
+        }
+        .pack(),
+    })
+}
+/// Creates a `FlashLoan` instruction
+///
+/// `host_fee_receiver_pubkey` should be one of the reserve's `ReserveFees::host_fee_receivers`.
+/// When no host is given, the flash loan fee receiver is passed in its place.
+/// `flash_loan_receiver_accounts` are appended after the fixed accounts and forwarded to the
+/// receiver program together with `call_back_data`.
+#[allow(clippy::too_many_arguments)]
+pub fn flash_loan(
+    program_id: Pubkey,
+    amount: u64,
+    source_liquidity_pubkey: Pubkey,
+    destination_liquidity_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    flash_loan_fee_receiver_pubkey: Pubkey,
+    host_fee_receiver_pubkey: Option<Pubkey>,
+    lending_market_pubkey: Pubkey,
+    lending_market_authority_pubkey: Pubkey,
+    flash_loan_receiver_program_id: Pubkey,
+    flash_loan_authority_pubkey: Pubkey,
+    flash_loan_receiver_accounts: Vec<AccountMeta>,
+    call_back_data: Vec<u8>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: FlashLoanAccounts {
+            source_liquidity: source_liquidity_pubkey,
+            destination_liquidity: destination_liquidity_pubkey,
+            reserve: reserve_pubkey,
+            flash_loan_fee_receiver: flash_loan_fee_receiver_pubkey,
+            host_fee_receiver: host_fee_receiver_pubkey.unwrap_or(flash_loan_fee_receiver_pubkey),
+            lending_market: lending_market_pubkey,
+            lending_market_authority: lending_market_authority_pubkey,
+            token_program: spl_token::id(),
+            flash_loan_receiver_program: flash_loan_receiver_program_id,
+            flash_loan_authority: flash_loan_authority_pubkey,
+            flash_loan_receiver_accounts,
+        }
+        .to_account_metas(),
+        data: LendingInstruction::FlashLoan { amount, call_back_data }.pack(),
+    }
+}
+/// Creates a `ClaimObligationMine` instruction
 pub fn claim_obligation_mine(
     program_id: Pubkey,
     obligation_pubkey:Pubkey,
@@ -935,19 +1573,642 @@ pub fn claim_obligation_mine(
     lending_market_pubkey:Pubkey,
     lending_market_authority_pubkey:Pubkey,
 ) ->Instruction {
-    let accounts = vec![
-        AccountMeta::new(obligation_pubkey, false),
-        AccountMeta::new(mine_supply_pubkey, false),
-        AccountMeta::new(destination_account_pubkey, false),
-        AccountMeta::new_readonly(obligation_owner, false),
-        AccountMeta::new_readonly(lending_market_pubkey, false),
-        AccountMeta::new_readonly(lending_market_authority_pubkey, false),
-        AccountMeta::new_readonly(spl_token::id(), false),
-    ];
     Instruction {
         program_id,
-        accounts,
+        accounts: ClaimObligationMineAccounts {
+            obligation: obligation_pubkey,
+            mine_supply: mine_supply_pubkey,
+            destination: destination_account_pubkey,
+            obligation_owner,
+            lending_market: lending_market_pubkey,
+            lending_market_authority: lending_market_authority_pubkey,
+            token_program: spl_token::id(),
+        }
+        .to_account_metas(),
         data: LendingInstruction::ClaimObligationMine.pack(),
     }
 }
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn all_instructions() -> Vec<LendingInstruction> {
+        vec![
+            LendingInstruction::InitLendingMarket {
+                owner: Pubkey::new_unique(),
+                quote_currency: *b"USD\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
+            },
+            LendingInstruction::SetLendingMarketOwner {
+                new_owner: Pubkey::new_unique(),
+            },
+            LendingInstruction::InitReserve {
+                config: ReserveConfig {
+                    optimal_utilization_rate: 80,
+                    loan_to_value_ratio: 75,
+                    liquidation_bonus: 5,
+                    liquidation_threshold: 85,
+                    min_borrow_rate: 1,
+                    optimal_borrow_rate: 22,
+                    max_borrow_rate: 47,
+                    fees: ReserveFees {
+                        borrow_fee_wad: 10_000_000_000_000,
+                        reserve_owner_fee_wad: 200_000_000_000_000_000,
+                        flash_loan_fee_wad: 3_000_000_000_000_000,
+                        host_fee_percentage: 20,
+                        host_fee_receivers: vec![],
+                    },
+                    ..ReserveConfig::default()
+                },
+                total_mining_speed: 46,
+                kink_util_rate: 50,
+                use_pyth_oracle: true,
+                is_lp: false,
+            },
+            LendingInstruction::RefreshReserve,
+            LendingInstruction::DepositReserveLiquidity { liquidity_amount: 1 },
+            LendingInstruction::RedeemReserveCollateral { collateral_amount: 2 },
+            LendingInstruction::InitObligation,
+            LendingInstruction::RefreshObligation,
+            LendingInstruction::DepositObligationCollateral { collateral_amount: 3 },
+            LendingInstruction::WithdrawObligationCollateral { collateral_amount: u64::MAX },
+            LendingInstruction::BorrowObligationLiquidity { liquidity_amount: 4 },
+            LendingInstruction::RepayObligationLiquidity { liquidity_amount: 5 },
+            LendingInstruction::LiquidateObligation { liquidity_amount: 6 },
+            LendingInstruction::FlashLoan {
+                amount: 7,
+                call_back_data: vec![0, 1, 2, 3],
+            },
+            LendingInstruction::SetConfig {
+                config_data: vec![],
+            },
+            LendingInstruction::SetConfig {
+                config_data: vec![1, 70],
+            },
+            LendingInstruction::InitMining,
+            LendingInstruction::RefreshMining,
+            LendingInstruction::DepositMining { amount: 8 },
+            LendingInstruction::WithdrawMining { amount: 9 },
+            LendingInstruction::ClaimMiningMine,
+            LendingInstruction::ClaimObligationMine,
+            LendingInstruction::ClaimOwnerFee,
+            LendingInstruction::ReceivePendingOwner,
+            LendingInstruction::RefreshReserves,
+            LendingInstruction::LiquidateObligation2 { liquidity_amount: 10 },
+            LendingInstruction::ClaimMine {
+                claim_times: 150,
+                claim_ratio: 10_000,
+            },
+        ]
+    }
+
+    #[test]
+    fn pack_unpack_round_trip() {
+        for instruction in all_instructions() {
+            let packed = instruction.pack();
+            assert_eq!(LendingInstruction::unpack(&packed).unwrap(), instruction);
+            assert_eq!(LendingInstruction::unpack(&packed).unwrap().pack(), packed);
+        }
+    }
+
+    #[test]
+    fn pack_init_reserve_layout() {
+        let mut data = vec![2, 80, 75, 5, 85, 1, 22, 47];
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.extend_from_slice(&2u64.to_le_bytes());
+        data.extend_from_slice(&3u64.to_le_bytes());
+        data.push(20);
+        data.extend_from_slice(&46u64.to_le_bytes());
+        data.extend_from_slice(&50u64.to_le_bytes());
+        data.extend_from_slice(&[1, 0]);
+
+        let instruction = LendingInstruction::unpack(&data).unwrap();
+        assert_eq!(instruction.pack(), data);
+    }
+
+    #[test]
+    fn pack_flash_loan_keeps_call_back_data() {
+        let packed = LendingInstruction::FlashLoan {
+            amount: u64::MAX,
+            call_back_data: vec![9, 8, 7],
+        }
+        .pack();
+        assert_eq!(packed[0], 13);
+        assert_eq!(packed[1..9], u64::MAX.to_le_bytes());
+        assert_eq!(packed[9..], [9, 8, 7]);
+    }
+
+    #[test]
+    fn liquidate_obligation2_accounts() {
+        let program_id = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        let instruction = liquidate_obligation2(
+            program_id,
+            u64::MAX,
+            keys[0],
+            keys[1],
+            keys[2],
+            keys[3],
+            keys[4],
+            keys[5],
+            keys[6],
+            keys[7],
+            keys[8],
+            keys[9],
+        );
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts.len(), 11);
+        for (meta, key) in instruction.accounts.iter().zip(keys.iter()) {
+            assert_eq!(meta.pubkey, *key);
+        }
+        assert_eq!(instruction.accounts[10].pubkey, spl_token::id());
+
+        let writable: Vec<bool> = instruction.accounts.iter().map(|a| a.is_writable).collect();
+        assert_eq!(
+            writable,
+            vec![true, true, true, true, false, true, true, false, false, false, false]
+        );
+        let signers: Vec<bool> = instruction.accounts.iter().map(|a| a.is_signer).collect();
+        assert_eq!(
+            signers,
+            vec![false, false, false, false, false, false, false, false, false, true, false]
+        );
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::LiquidateObligation2 {
+                liquidity_amount: u64::MAX
+            }
+        );
+    }
+
+    #[test]
+    fn flash_loan_accounts() {
+        let program_id = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..9).map(|_| Pubkey::new_unique()).collect();
+        let extra = vec![
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), true),
+        ];
+        let instruction = flash_loan(
+            program_id,
+            100,
+            keys[0],
+            keys[1],
+            keys[2],
+            keys[3],
+            Some(keys[4]),
+            keys[5],
+            keys[6],
+            keys[7],
+            keys[8],
+            extra.clone(),
+            vec![1, 2, 3],
+        );
+        let pubkeys: Vec<Pubkey> = instruction.accounts.iter().map(|a| a.pubkey).collect();
+        assert_eq!(
+            pubkeys[..10],
+            [
+                keys[0],
+                keys[1],
+                keys[2],
+                keys[3],
+                keys[4],
+                keys[5],
+                keys[6],
+                spl_token::id(),
+                keys[7],
+                keys[8]
+            ]
+        );
+        assert_eq!(instruction.accounts[10..], extra[..]);
+        assert!(instruction.accounts[..5].iter().all(|a| a.is_writable));
+        assert!(instruction.accounts[5..10].iter().all(|a| !a.is_writable));
+        assert!(instruction.accounts[9].is_signer);
+        assert!(instruction.accounts[..9].iter().all(|a| !a.is_signer));
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::FlashLoan {
+                amount: 100,
+                call_back_data: vec![1, 2, 3]
+            }
+        );
+
+        let instruction = flash_loan(
+            program_id,
+            100,
+            keys[0],
+            keys[1],
+            keys[2],
+            keys[3],
+            None,
+            keys[5],
+            keys[6],
+            keys[7],
+            keys[8],
+            vec![],
+            vec![],
+        );
+        assert_eq!(instruction.accounts.len(), 10);
+        assert_eq!(instruction.accounts[4].pubkey, keys[3]);
+    }
+
+    #[test]
+    fn mining_instructions_accounts() {
+        let program_id = Pubkey::new_unique();
+        let mining = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let lending_market = Pubkey::new_unique();
+        let reserves = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let instruction = init_mining(program_id, mining, owner, lending_market);
+        assert_eq!(instruction.data, vec![16]);
+        assert!(instruction.accounts[0].is_writable);
+        assert!(instruction.accounts[1].is_signer);
+
+        let instruction = refresh_mining(program_id, mining, reserves.clone());
+        assert_eq!(instruction.data, vec![17]);
+        assert_eq!(instruction.accounts.len(), 3);
+        assert!(instruction.accounts[0].is_writable);
+        assert_eq!(instruction.accounts[1].pubkey, reserves[0]);
+        assert_eq!(instruction.accounts[2].pubkey, reserves[1]);
+        assert!(instruction.accounts[1..].iter().all(|a| !a.is_writable));
+
+        let keys: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+        let instruction = deposit_mining(
+            program_id, 5, keys[0], keys[1], keys[2], keys[3], keys[4], keys[5], keys[6],
+        );
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::DepositMining { amount: 5 }
+        );
+        let writable: Vec<bool> = instruction.accounts.iter().map(|a| a.is_writable).collect();
+        assert_eq!(writable, vec![true, true, true, false, false, false, false, false]);
+        let signers: Vec<bool> = instruction.accounts.iter().map(|a| a.is_signer).collect();
+        assert_eq!(signers, vec![false, false, false, false, false, false, true, false]);
+
+        let instruction = withdraw_mining(
+            program_id, 6, keys[0], keys[1], keys[2], keys[3], keys[4], keys[5], keys[6],
+        );
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::WithdrawMining { amount: 6 }
+        );
+        let writable: Vec<bool> = instruction.accounts.iter().map(|a| a.is_writable).collect();
+        assert_eq!(writable, vec![true, true, true, true, false, false, false, false]);
+        let signers: Vec<bool> = instruction.accounts.iter().map(|a| a.is_signer).collect();
+        assert_eq!(signers, vec![false, false, false, false, false, false, true, false]);
+
+        let instruction = claim_mining_mine(
+            program_id,
+            keys[0],
+            keys[1],
+            keys[2],
+            keys[3],
+            keys[4],
+            keys[5],
+            reserves.clone(),
+        );
+        assert_eq!(instruction.data, vec![20]);
+        assert_eq!(instruction.accounts.len(), 9);
+        assert_eq!(instruction.accounts[6].pubkey, spl_token::id());
+        assert!(instruction.accounts[3].is_signer);
+        assert_eq!(instruction.accounts[7].pubkey, reserves[0]);
+        assert_eq!(instruction.accounts[8].pubkey, reserves[1]);
+    }
+
+    #[test]
+    fn claim_mine_accounts() {
+        let program_id = Pubkey::new_unique();
+        let lending_market = LendingMarket {
+            mine_supply_account: Pubkey::new_unique(),
+            mine_lock_program: Pubkey::new_unique(),
+            max_claim_times: 200,
+            ..LendingMarket::default()
+        };
+        let keys: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+
+        let instruction = claim_mine(
+            program_id,
+            100,
+            10_000,
+            keys[0],
+            &lending_market,
+            keys[1],
+            keys[2],
+            keys[3],
+            Some(keys[4]),
+            ClaimMineTarget::Destination {
+                destination_pubkey: keys[5],
+            },
+        )
+        .unwrap();
+        let pubkeys: Vec<Pubkey> = instruction.accounts.iter().map(|a| a.pubkey).collect();
+        assert_eq!(
+            pubkeys,
+            vec![
+                spl_token::id(),
+                keys[0],
+                keys[1],
+                keys[2],
+                keys[3],
+                keys[4],
+                keys[5],
+                lending_market.mine_supply_account
+            ]
+        );
+        assert!(instruction.accounts[3].is_signer);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::ClaimMine {
+                claim_times: 100,
+                claim_ratio: 10_000
+            }
+        );
+
+        let instruction = claim_mine(
+            program_id,
+            200,
+            5_000,
+            keys[0],
+            &lending_market,
+            keys[1],
+            keys[2],
+            keys[3],
+            None,
+            ClaimMineTarget::LockProgram {
+                lock_pool_pubkey: keys[5],
+                user_info_pubkey: keys[6],
+            },
+        )
+        .unwrap();
+        let pubkeys: Vec<Pubkey> = instruction.accounts.iter().map(|a| a.pubkey).collect();
+        assert_eq!(
+            pubkeys,
+            vec![
+                spl_token::id(),
+                keys[0],
+                keys[1],
+                keys[2],
+                keys[3],
+                lending_market.mine_lock_program,
+                keys[5],
+                keys[6]
+            ]
+        );
+        assert!(!instruction.accounts[5].is_writable);
+        assert!(instruction.accounts[6].is_writable);
+    }
+
+    #[test]
+    fn claim_mine_invalid_amounts() {
+        let lending_market = LendingMarket {
+            max_claim_times: 200,
+            ..LendingMarket::default()
+        };
+        let target = ClaimMineTarget::Destination {
+            destination_pubkey: Pubkey::new_unique(),
+        };
+        for (claim_times, claim_ratio) in [(99, 10_000), (201, 10_000), (100, 10_001)] {
+            assert_eq!(
+                claim_mine(
+                    Pubkey::new_unique(),
+                    claim_times,
+                    claim_ratio,
+                    Pubkey::new_unique(),
+                    &lending_market,
+                    Pubkey::new_unique(),
+                    Pubkey::new_unique(),
+                    Pubkey::new_unique(),
+                    None,
+                    target,
+                ),
+                Err(LendingError::InvalidAmount.into())
+            );
+        }
+    }
+
+    #[test]
+    fn admin_instructions_accounts() {
+        let program_id = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..14).map(|_| Pubkey::new_unique()).collect();
+        let quote_currency = *b"USD\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
+
+        let instruction = init_lending_market(
+            program_id,
+            keys[0],
+            quote_currency,
+            keys[1],
+            keys[2],
+            keys[3],
+            keys[4],
+            keys[5],
+        );
+        assert_eq!(instruction.accounts.len(), 7);
+        assert!(instruction.accounts[0].is_signer);
+        assert!(instruction.accounts[1].is_writable);
+        assert_eq!(instruction.accounts[2].pubkey, sysvar::rent::id());
+        assert_eq!(instruction.accounts[3].pubkey, spl_token::id());
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::InitLendingMarket {
+                owner: keys[0],
+                quote_currency
+            }
+        );
+
+        let instruction = set_lending_market_owner(program_id, keys[0], keys[1], keys[2]);
+        assert!(instruction.accounts[0].is_writable);
+        assert!(instruction.accounts[1].is_signer);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::SetLendingMarketOwner { new_owner: keys[2] }
+        );
+
+        let instruction = receive_pending_owner(program_id, keys[0], keys[1]);
+        assert!(instruction.accounts[0].is_writable);
+        assert!(instruction.accounts[1].is_signer);
+        assert_eq!(instruction.data, vec![23]);
+
+        let instruction = claim_owner_fee(program_id, keys[0], keys[1], keys[2], keys[3]);
+        assert_eq!(instruction.accounts.len(), 4);
+        assert!(instruction.accounts[3].is_signer);
+        assert_eq!(instruction.data, vec![22]);
+    }
+
+    #[test]
+    fn init_reserve_accounts() {
+        use crate::state::reserve::init_reserve_accounts_index::*;
+
+        let program_id = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..12).map(|_| Pubkey::new_unique()).collect();
+        let config = ReserveConfig {
+            optimal_utilization_rate: 90,
+            loan_to_value_ratio: 75,
+            liquidation_bonus: 8,
+            liquidation_threshold: 80,
+            min_borrow_rate: 2,
+            optimal_borrow_rate: 22,
+            max_borrow_rate: 47,
+            ..ReserveConfig::default()
+        };
+        let instruction = init_reserve(
+            program_id,
+            config.clone(),
+            46,
+            50,
+            true,
+            false,
+            keys[0],
+            keys[1],
+            keys[2],
+            keys[3],
+            keys[4],
+            keys[5],
+            keys[6],
+            keys[7],
+            keys[8],
+            keys[9],
+            keys[10],
+            keys[11],
+        );
+        let accounts = &instruction.accounts;
+        assert_eq!(accounts.len(), TOKEN_PROGRAM_ID + 1);
+        assert_eq!(accounts[RESERVE_ACCOUNT].pubkey, keys[0]);
+        assert_eq!(accounts[LIQUIDITY_MINT].pubkey, keys[1]);
+        assert_eq!(accounts[LIQUIDITY_SUPPLY].pubkey, keys[2]);
+        assert_eq!(accounts[LIQUIDITY_FEE_RECEIVER].pubkey, keys[3]);
+        assert_eq!(accounts[PYTH_PRODUCT].pubkey, keys[4]);
+        assert_eq!(accounts[PYTH_PRICE].pubkey, keys[5]);
+        assert_eq!(accounts[LARIX_ORACLE].pubkey, keys[6]);
+        assert_eq!(accounts[COLLATERAL_MINT].pubkey, keys[7]);
+        assert_eq!(accounts[COLLATERAL_SUPPLY].pubkey, keys[8]);
+        assert_eq!(accounts[LENDING_MARKET].pubkey, keys[9]);
+        assert_eq!(accounts[LENDING_MARKET_OWNER].pubkey, keys[10]);
+        assert!(accounts[LENDING_MARKET_OWNER].is_signer);
+        assert_eq!(accounts[UN_COLL_SUPPLY].pubkey, keys[11]);
+        assert_eq!(accounts[CLOCK_SYSVAR].pubkey, sysvar::clock::id());
+        assert_eq!(accounts[RENT_SYSVAR].pubkey, sysvar::rent::id());
+        assert_eq!(accounts[TOKEN_PROGRAM_ID].pubkey, spl_token::id());
+        assert!(accounts[RESERVE_ACCOUNT].is_writable);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::InitReserve {
+                config,
+                total_mining_speed: 46,
+                kink_util_rate: 50,
+                use_pyth_oracle: true,
+                is_lp: false
+            }
+        );
+    }
+
+    #[test]
+    fn set_config_passes_payload_through() {
+        let program_id = Pubkey::new_unique();
+        let accounts = vec![
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), true),
+        ];
+
+        let instruction = set_config(program_id, vec![6, 47], accounts.clone());
+        assert_eq!(instruction.accounts, accounts);
+        assert_eq!(instruction.data, vec![14, 6, 47]);
+        assert_eq!(
+            LendingInstruction::unpack(&instruction.data).unwrap(),
+            LendingInstruction::SetConfig {
+                config_data: vec![6, 47]
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Each reserve must have an oracle account")]
+    fn refresh_reserves_missing_oracle() {
+        refresh_reserves(
+            crate::id(),
+            vec![Pubkey::new_unique(), Pubkey::new_unique()],
+            vec![Pubkey::new_unique()],
+        );
+    }
+
+    #[test]
+    fn claim_obligation_mine_accounts() {
+        let keys: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        let instruction = claim_obligation_mine(
+            crate::id(),
+            keys[0],
+            keys[1],
+            keys[2],
+            keys[3],
+            keys[4],
+            keys[5],
+        );
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(keys[0], false),
+                AccountMeta::new(keys[1], false),
+                AccountMeta::new(keys[2], false),
+                AccountMeta::new_readonly(keys[3], false),
+                AccountMeta::new_readonly(keys[4], false),
+                AccountMeta::new_readonly(keys[5], false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn instruction_tags() {
+        let known: Vec<u8> = (0..=26).filter(|tag| *tag != 15).collect();
+        for tag in 0..=u8::MAX {
+            match LendingInstructionTag::try_from(tag) {
+                Ok(instruction_tag) => {
+                    assert!(known.contains(&tag));
+                    assert_eq!(instruction_tag as u8, tag);
+                }
+                Err(error) => {
+                    assert!(!known.contains(&tag));
+                    assert_eq!(error, UnpackError::UnknownTag(tag));
+                }
+            }
+        }
+
+        let mut packed_tags = vec![];
+        for instruction in all_instructions() {
+            let tag = instruction.tag() as u8;
+            assert_eq!(instruction.pack()[0], tag);
+            if !packed_tags.contains(&tag) {
+                packed_tags.push(tag);
+            }
+        }
+        packed_tags.sort_unstable();
+        assert_eq!(packed_tags, known);
+    }
+
+    #[test]
+    fn unpack_unknown_tag() {
+        for tag in [15, 27, u8::MAX] {
+            let input = [tag, 0, 0, 0, 0, 0, 0, 0, 0];
+            assert_eq!(
+                LendingInstruction::try_unpack(&input),
+                Err(UnpackError::UnknownTag(tag))
+            );
+            assert_eq!(
+                LendingInstruction::unpack(&input),
+                Err(LendingError::UnknownInstructionTag.into())
+            );
+        }
+        assert_eq!(
+            LendingInstruction::try_unpack(&[]),
+            Err(UnpackError::InvalidData(LendingError::InstructionUnpackError.into()))
+        );
+        assert_eq!(
+            LendingInstruction::unpack(&[]),
+            Err(LendingError::InstructionUnpackError.into())
+        );
+        assert_eq!(
+            LendingInstruction::try_unpack(&[LendingInstructionTag::ClaimMine as u8, 100]),
+            Err(UnpackError::InvalidData(LendingError::InstructionUnpackError.into()))
+        );
+    }
+}
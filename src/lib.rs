@@ -5,12 +5,17 @@
 // Export current sdk types for downstream users building with a different sdk version
 pub use solana_program;
 
+pub mod accounts;
+pub mod anchor;
 pub mod entrypoint;
 pub mod error;
 pub mod instruction;
 pub mod math;
 pub mod state;
+pub mod transaction;
+#[cfg(any(test, feature = "config"))]
 pub mod config;
+pub mod cpi;
 pub mod util;
 
 
@@ -1,4 +1,5 @@
 use super::*;
+use crate::error::LendingError;
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::{
     msg,
@@ -7,6 +8,11 @@ use solana_program::{
     pubkey::{Pubkey, PUBKEY_BYTES},
 };
 
+/// Minimum claim times of a `ClaimMine` instruction, 100 equals claiming 1 time
+pub const MIN_CLAIM_TIMES: u16 = 100;
+/// Scale of the claim ratio of a `ClaimMine` instruction, 10000 equals 100%
+pub const MAX_CLAIM_RATIO: u16 = 10_000;
+
 /// Lending market state
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct LendingMarket {
@@ -65,6 +71,46 @@ impl LendingMarket {
         self.mine_supply_account = params.mine_supply_account;
         self.mine_lock_program = params.mine_lock_program;
     }
+
+    /// Checks the `claim_times` and `claim_ratio` of a `ClaimMine` instruction
+    pub fn check_claim_mine(&self, claim_times: u16, claim_ratio: u16) -> Result<(), ProgramError> {
+        if claim_times < MIN_CLAIM_TIMES || claim_times > self.max_claim_times {
+            msg!("Claim times must be between {} and {}", MIN_CLAIM_TIMES, self.max_claim_times);
+            return Err(LendingError::InvalidAmount.into());
+        }
+        if claim_ratio > MAX_CLAIM_RATIO {
+            msg!("Claim ratio cannot exceed {}", MAX_CLAIM_RATIO);
+            return Err(LendingError::InvalidAmount.into());
+        }
+        Ok(())
+    }
+}
+
+/// Finds the derived lending market authority and its bump seed
+pub fn find_lending_market_authority(
+    program_id: &Pubkey,
+    lending_market_pubkey: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[lending_market_pubkey.as_ref()], program_id)
+}
+
+/// Creates the derived lending market authority from a known bump seed,
+/// e.g. `LendingMarket::bump_seed`
+pub fn create_lending_market_authority(
+    program_id: &Pubkey,
+    lending_market_pubkey: &Pubkey,
+    bump_seed: u8,
+) -> Result<Pubkey, ProgramError> {
+    Pubkey::create_program_address(&[lending_market_pubkey.as_ref(), &[bump_seed]], program_id)
+        .map_err(|_| {
+            msg!("Lending market bump seed does not derive a valid authority");
+            LendingError::InvalidMarketAuthority.into()
+        })
+}
+
+/// Derived lending market authority of a lending market owned by this program
+pub fn get_lending_market_authority(lending_market_pubkey: &Pubkey) -> Pubkey {
+    find_lending_market_authority(&crate::id(), lending_market_pubkey).0
 }
 
 /// Initialize a lending market
@@ -222,3 +268,61 @@ impl Pack for LendingMarket {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    // Mainnet lending market and authority from config/lending-market-mainnet-product.json
+    const MAINNET_LENDING_MARKET: &str = "5geyZJdffDBNoMqEbogbPvdgH9ue7NREobtW8M3C1qfe";
+    const MAINNET_LENDING_MARKET_AUTHORITY: &str = "BxnUi6jyYbtEEgkBq4bPLKzDpSfWVAzgyf3TF2jfC1my";
+
+    #[test]
+    fn mainnet_lending_market_authority() {
+        let lending_market = Pubkey::from_str(MAINNET_LENDING_MARKET).unwrap();
+        let authority = Pubkey::from_str(MAINNET_LENDING_MARKET_AUTHORITY).unwrap();
+
+        let (found, bump_seed) = find_lending_market_authority(&crate::id(), &lending_market);
+        assert_eq!(found, authority);
+        assert_eq!(get_lending_market_authority(&lending_market), authority);
+        assert_eq!(
+            create_lending_market_authority(&crate::id(), &lending_market, bump_seed),
+            Ok(authority)
+        );
+    }
+
+    #[test]
+    fn claim_mine_checks() {
+        let lending_market = LendingMarket {
+            max_claim_times: 200,
+            ..LendingMarket::default()
+        };
+        assert_eq!(lending_market.check_claim_mine(MIN_CLAIM_TIMES, MAX_CLAIM_RATIO), Ok(()));
+        assert_eq!(lending_market.check_claim_mine(200, 0), Ok(()));
+        assert_eq!(
+            lending_market.check_claim_mine(99, 0),
+            Err(LendingError::InvalidAmount.into())
+        );
+        assert_eq!(
+            lending_market.check_claim_mine(201, 0),
+            Err(LendingError::InvalidAmount.into())
+        );
+        assert_eq!(
+            lending_market.check_claim_mine(100, MAX_CLAIM_RATIO + 1),
+            Err(LendingError::InvalidAmount.into())
+        );
+    }
+
+    #[test]
+    fn lending_market_authority_round_trip() {
+        let program_id = Pubkey::new_unique();
+        let lending_market = Pubkey::new_unique();
+        let (authority, bump_seed) = find_lending_market_authority(&program_id, &lending_market);
+        assert_eq!(
+            create_lending_market_authority(&program_id, &lending_market, bump_seed),
+            Ok(authority)
+        );
+        assert_ne!(get_lending_market_authority(&lending_market), authority);
+    }
+}
@@ -0,0 +1,273 @@
+use super::*;
+use crate::{error::LendingError, math::Decimal};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use solana_program::{
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::{Pubkey, PUBKEY_BYTES},
+};
+use std::convert::TryFrom;
+
+/// Max number of reserves a mining account can stake LTokens of
+pub const MAX_MINING_RESERVES: usize = 10;
+
+/// Mining state of un-collateralized LTokens staked through `DepositMining`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mining {
+    /// Version of the struct
+    pub version: u8,
+    /// Owner authority which can withdraw LTokens and claim mine
+    pub owner: Pubkey,
+    /// Lending market address
+    pub lending_market: Pubkey,
+    /// Staked LTokens and mining index of each reserve, unique by reserve address
+    pub mining_indices: Vec<MiningIndex>,
+    /// Total unclaimed mine of the staked LTokens
+    pub unclaimed_mine: Decimal,
+}
+
+impl Mining {
+    /// Create a new mining account
+    pub fn new(params: InitMiningParams) -> Self {
+        let mut mining = Self::default();
+        Self::init(&mut mining, params);
+        mining
+    }
+
+    /// Initialize a mining account
+    pub fn init(&mut self, params: InitMiningParams) {
+        self.version = PROGRAM_VERSION;
+        self.owner = params.owner;
+        self.lending_market = params.lending_market;
+        self.mining_indices = vec![];
+        self.unclaimed_mine = Decimal::zero();
+    }
+
+    /// Find the staked LTokens of a reserve
+    pub fn find_mining_index(&self, reserve: &Pubkey) -> Option<&MiningIndex> {
+        self.mining_indices
+            .iter()
+            .find(|mining_index| mining_index.reserve == *reserve)
+    }
+
+    /// Find or add the staked LTokens of a reserve, at most `MAX_MINING_RESERVES` reserves
+    pub fn find_or_add_mining_index(
+        &mut self,
+        reserve: &Pubkey,
+    ) -> Result<&mut MiningIndex, ProgramError> {
+        if let Some(position) = self
+            .mining_indices
+            .iter()
+            .position(|mining_index| mining_index.reserve == *reserve)
+        {
+            return Ok(&mut self.mining_indices[position]);
+        }
+        if self.mining_indices.len() >= MAX_MINING_RESERVES {
+            msg!("Mining cannot hold more than {} reserves", MAX_MINING_RESERVES);
+            return Err(LendingError::MiningVolumeLimit.into());
+        }
+        self.mining_indices.push(MiningIndex {
+            reserve: *reserve,
+            ..MiningIndex::default()
+        });
+        Ok(self.mining_indices.last_mut().unwrap())
+    }
+}
+
+/// Initialize a mining account
+pub struct InitMiningParams {
+    /// Owner authority which can withdraw LTokens and claim mine
+    pub owner: Pubkey,
+    /// Lending market address
+    pub lending_market: Pubkey,
+}
+
+/// LTokens of a reserve staked in a mining account
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MiningIndex {
+    /// Reserve the LTokens are minted by
+    pub reserve: Pubkey,
+    /// Amount of un-collateralized LTokens staked
+    pub un_coll_l_token_amount: u64,
+    /// `Bonus::l_token_mining_index` of the reserve when the mine was last settled
+    pub index: Decimal,
+}
+
+impl Sealed for Mining {}
+impl IsInitialized for Mining {
+    fn is_initialized(&self) -> bool {
+        self.version != UNINITIALIZED_VERSION
+    }
+}
+
+// Not yet checked against the data of a mainnet mining account
+const MINING_INDEX_LEN: usize = 56; // 32 + 8 + 16
+const MINING_LEN: usize = 642; // 1 + 32 + 32 + 1 + 16 + (56 * 10)
+impl Pack for Mining {
+    const LEN: usize = MINING_LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let output = array_mut_ref![dst, 0, MINING_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (version, owner, lending_market, mining_indices_len, unclaimed_mine, data_flat) = mut_array_refs![
+            output,
+            1,
+            PUBKEY_BYTES,
+            PUBKEY_BYTES,
+            1,
+            16,
+            MINING_INDEX_LEN * MAX_MINING_RESERVES
+        ];
+
+        *version = self.version.to_le_bytes();
+        owner.copy_from_slice(self.owner.as_ref());
+        lending_market.copy_from_slice(self.lending_market.as_ref());
+        *mining_indices_len = u8::try_from(self.mining_indices.len()).unwrap().to_le_bytes();
+        pack_decimal(self.unclaimed_mine, unclaimed_mine);
+
+        let mut offset = 0;
+        for mining_index in &self.mining_indices {
+            let mining_index_flat = array_mut_ref![data_flat, offset, MINING_INDEX_LEN];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (reserve, un_coll_l_token_amount, index) =
+                mut_array_refs![mining_index_flat, PUBKEY_BYTES, 8, 16];
+            reserve.copy_from_slice(mining_index.reserve.as_ref());
+            *un_coll_l_token_amount = mining_index.un_coll_l_token_amount.to_le_bytes();
+            pack_decimal(mining_index.index, index);
+            offset += MINING_INDEX_LEN;
+        }
+    }
+
+    /// Unpacks a byte buffer into a [Mining](struct.Mining.html).
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let input = array_ref![src, 0, MINING_LEN];
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (version, owner, lending_market, mining_indices_len, unclaimed_mine, data_flat) = array_refs![
+            input,
+            1,
+            PUBKEY_BYTES,
+            PUBKEY_BYTES,
+            1,
+            16,
+            MINING_INDEX_LEN * MAX_MINING_RESERVES
+        ];
+
+        let version = u8::from_le_bytes(*version);
+        if version > PROGRAM_VERSION {
+            msg!("Mining version does not match lending program version");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mining_indices_len = u8::from_le_bytes(*mining_indices_len) as usize;
+        if mining_indices_len > MAX_MINING_RESERVES {
+            msg!("Mining cannot hold more than {} reserves", MAX_MINING_RESERVES);
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut mining_indices = Vec::with_capacity(mining_indices_len);
+        let mut offset = 0;
+        for _ in 0..mining_indices_len {
+            let mining_index_flat = array_ref![data_flat, offset, MINING_INDEX_LEN];
+            #[allow(clippy::ptr_offset_with_cast)]
+            let (reserve, un_coll_l_token_amount, index) =
+                array_refs![mining_index_flat, PUBKEY_BYTES, 8, 16];
+            let reserve = Pubkey::new_from_array(*reserve);
+            if mining_indices
+                .iter()
+                .any(|mining_index: &MiningIndex| mining_index.reserve == reserve)
+            {
+                msg!("Mining reserve {} is listed more than once", reserve);
+                return Err(ProgramError::InvalidAccountData);
+            }
+            mining_indices.push(MiningIndex {
+                reserve,
+                un_coll_l_token_amount: u64::from_le_bytes(*un_coll_l_token_amount),
+                index: unpack_decimal(index),
+            });
+            offset += MINING_INDEX_LEN;
+        }
+
+        Ok(Self {
+            version,
+            owner: Pubkey::new_from_array(*owner),
+            lending_market: Pubkey::new_from_array(*lending_market),
+            mining_indices,
+            unclaimed_mine: unpack_decimal(unclaimed_mine),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mining_pack_round_trip() {
+        let mut mining = Mining::new(InitMiningParams {
+            owner: Pubkey::new_unique(),
+            lending_market: Pubkey::new_unique(),
+        });
+        assert!(mining.is_initialized());
+        mining.unclaimed_mine = Decimal::from(12u64);
+        mining.mining_indices = (0..MAX_MINING_RESERVES as u64)
+            .map(|i| MiningIndex {
+                reserve: Pubkey::new_unique(),
+                un_coll_l_token_amount: i * 1_000,
+                index: Decimal::from(i),
+            })
+            .collect();
+
+        let mut packed = [0u8; Mining::LEN];
+        Mining::pack(mining.clone(), &mut packed).unwrap();
+        assert_eq!(Mining::unpack(&packed), Ok(mining.clone()));
+        assert_eq!(
+            mining.find_mining_index(&mining.mining_indices[3].reserve),
+            Some(&mining.mining_indices[3])
+        );
+    }
+
+    #[test]
+    fn mining_find_or_add_index() {
+        let mut mining = Mining::default();
+        let reserves: Vec<Pubkey> = (0..MAX_MINING_RESERVES)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        for reserve in &reserves {
+            mining.find_or_add_mining_index(reserve).unwrap().un_coll_l_token_amount = 10;
+        }
+        mining.find_or_add_mining_index(&reserves[2]).unwrap().un_coll_l_token_amount += 5;
+        assert_eq!(mining.mining_indices.len(), MAX_MINING_RESERVES);
+        assert_eq!(
+            mining.find_mining_index(&reserves[2]).map(|index| index.un_coll_l_token_amount),
+            Some(15)
+        );
+        assert_eq!(
+            mining.find_or_add_mining_index(&Pubkey::new_unique()),
+            Err(LendingError::MiningVolumeLimit.into())
+        );
+        assert_eq!(mining.mining_indices.len(), MAX_MINING_RESERVES);
+    }
+
+    #[test]
+    fn mining_unpack_invalid() {
+        let mut packed = [0u8; Mining::LEN];
+        assert_eq!(
+            Mining::unpack(&packed),
+            Err(ProgramError::UninitializedAccount)
+        );
+
+        packed[0] = PROGRAM_VERSION;
+        packed[1 + 2 * PUBKEY_BYTES] = MAX_MINING_RESERVES as u8 + 1;
+        assert_eq!(
+            Mining::unpack(&packed),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        // two indices of the same reserve
+        packed[1 + 2 * PUBKEY_BYTES] = 2;
+        assert_eq!(
+            Mining::unpack(&packed),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
@@ -1,10 +1,11 @@
 use solana_program::msg;
 use solana_program::program_error::ProgramError;
-use crate::math::{Decimal};
+use crate::math::{Decimal, WAD};
 
 pub mod obligation;
 pub mod last_update;
 pub mod lending_market;
+pub mod mining;
 pub mod reserve;
 
 /// Percentage of an obligation that can be repaid during each liquidation call
@@ -13,6 +14,7 @@ pub const LIQUIDATION_CLOSE_FACTOR: u8 = 50;
 /// Collateral tokens are initially valued at a ratio of 5:1 (collateral:liquidity)
 // @FIXME: restore to 5
 pub const INITIAL_COLLATERAL_RATIO: u64 = 1;
+const INITIAL_COLLATERAL_RATE: u64 = INITIAL_COLLATERAL_RATIO * WAD;
 
 /// Current version of the program and all new accounts created
 pub const PROGRAM_VERSION: u8 = 1;
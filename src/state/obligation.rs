@@ -1,17 +1,21 @@
 use super::*;
 use crate::{
-    math::{Decimal},
+    error::LendingError,
+    math::{Decimal, Rate, TryAdd, TryDiv, TryMul, TrySub},
+    state::reserve::Reserve,
 };
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::{
     clock::Slot,
+    entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
     pubkey::{Pubkey, PUBKEY_BYTES},
 };
 use std::{
-    convert::{TryFrom},
+    cmp::Ordering,
+    convert::{TryFrom, TryInto},
 };
 use crate::state::last_update::LastUpdate;
 
@@ -65,6 +69,233 @@ impl Obligation {
         self.deposits = params.deposits;
         self.borrows = params.borrows;
     }
+
+    /// Calculate the current ratio of borrowed value to deposited value. Returns `None` when
+    /// nothing is deposited
+    pub fn loan_to_value(&self) -> Result<Option<Decimal>, ProgramError> {
+        if self.deposited_value == Decimal::zero() {
+            return Ok(None);
+        }
+        self.borrowed_value.try_div(self.deposited_value).map(Some)
+    }
+
+    /// Calculate the ratio of unhealthy borrow value to borrowed value, the obligation can be
+    /// liquidated at or below 1. Returns `None` when nothing is borrowed
+    pub fn health_factor(&self) -> Result<Option<Decimal>, ProgramError> {
+        if self.borrowed_value == Decimal::zero() {
+            return Ok(None);
+        }
+        self.unhealthy_borrow_value
+            .try_div(self.borrowed_value)
+            .map(Some)
+    }
+
+    /// Check if the borrowed value reached the unhealthy borrow value
+    pub fn is_liquidatable(&self) -> bool {
+        self.borrowed_value > Decimal::zero() && self.borrowed_value >= self.unhealthy_borrow_value
+    }
+
+    /// Calculate the maximum liquidity value that can be borrowed
+    pub fn remaining_borrow_value(&self) -> Result<Decimal, ProgramError> {
+        if self.borrowed_value >= self.allowed_borrow_value {
+            return Ok(Decimal::zero());
+        }
+        self.allowed_borrow_value.try_sub(self.borrowed_value)
+    }
+
+    /// Calculate the maximum collateral value that can be withdrawn
+    pub fn max_withdraw_value(&self) -> Result<Decimal, ProgramError> {
+        if self.borrowed_value == Decimal::zero() {
+            return Ok(self.deposited_value);
+        }
+        if self.allowed_borrow_value == Decimal::zero() {
+            return Ok(Decimal::zero());
+        }
+        let required_deposit_value = self
+            .borrowed_value
+            .try_mul(self.deposited_value)?
+            .try_div(self.allowed_borrow_value)?;
+        if required_deposit_value >= self.deposited_value {
+            return Ok(Decimal::zero());
+        }
+        self.deposited_value.try_sub(required_deposit_value)
+    }
+
+    /// Calculate the maximum liquidity amount that can be liquidated
+    pub fn max_liquidation_amount(
+        &self,
+        liquidity: &ObligationLiquidity,
+    ) -> Result<Decimal, ProgramError> {
+        let max_liquidation_value = self
+            .borrowed_value
+            .try_mul(Rate::from_percent(LIQUIDATION_CLOSE_FACTOR))?
+            .min(liquidity.market_value);
+        let max_liquidation_pct = max_liquidation_value.try_div(liquidity.market_value)?;
+        liquidity.borrowed_amount_wads.try_mul(max_liquidation_pct)
+    }
+
+    /// Recalculate the market values of the obligation from the given reserves the same way
+    /// `RefreshObligation` does, accruing interest on the borrowed liquidity
+    pub fn refresh(&mut self, reserves: &[(Pubkey, Reserve)]) -> Result<(), ProgramError> {
+        let mut deposited_value = Decimal::zero();
+        let mut borrowed_value = Decimal::zero();
+        let mut allowed_borrow_value = Decimal::zero();
+        let mut unhealthy_borrow_value = Decimal::zero();
+
+        for collateral in &mut self.deposits {
+            let deposit_reserve = find_reserve(reserves, &collateral.deposit_reserve)?;
+            let liquidity_amount = deposit_reserve
+                .collateral_exchange_rate()?
+                .decimal_collateral_to_liquidity(collateral.deposited_amount.into())?;
+            let market_value = deposit_reserve.liquidity.market_value(liquidity_amount)?;
+            let loan_to_value_rate = Rate::from_percent(deposit_reserve.config.loan_to_value_ratio);
+            let liquidation_threshold_rate =
+                Rate::from_percent(deposit_reserve.config.liquidation_threshold);
+
+            collateral.market_value = market_value;
+            deposited_value = deposited_value.try_add(market_value)?;
+            allowed_borrow_value =
+                allowed_borrow_value.try_add(market_value.try_mul(loan_to_value_rate)?)?;
+            unhealthy_borrow_value =
+                unhealthy_borrow_value.try_add(market_value.try_mul(liquidation_threshold_rate)?)?;
+        }
+
+        for liquidity in &mut self.borrows {
+            let borrow_reserve = find_reserve(reserves, &liquidity.borrow_reserve)?;
+            liquidity.accrue_interest(borrow_reserve.liquidity.cumulative_borrow_rate_wads)?;
+            let market_value = borrow_reserve
+                .liquidity
+                .market_value(liquidity.borrowed_amount_wads)?;
+
+            liquidity.market_value = market_value;
+            borrowed_value = borrowed_value.try_add(market_value)?;
+        }
+
+        self.deposited_value = deposited_value;
+        self.borrowed_value = borrowed_value;
+        self.allowed_borrow_value = allowed_borrow_value;
+        self.unhealthy_borrow_value = unhealthy_borrow_value;
+        Ok(())
+    }
+
+    /// Withdraw collateral and remove it from deposits if zeroed out
+    pub fn withdraw(&mut self, withdraw_amount: u64, collateral_index: usize) -> ProgramResult {
+        let collateral = &mut self.deposits[collateral_index];
+        if withdraw_amount == collateral.deposited_amount {
+            self.deposits.remove(collateral_index);
+        } else {
+            collateral.withdraw(withdraw_amount)?;
+        }
+        Ok(())
+    }
+
+    /// Repay liquidity and remove it from borrows if zeroed out
+    pub fn repay(&mut self, settle_amount: Decimal, liquidity_index: usize) -> ProgramResult {
+        let liquidity = &mut self.borrows[liquidity_index];
+        if settle_amount == liquidity.borrowed_amount_wads {
+            self.borrows.remove(liquidity_index);
+        } else {
+            liquidity.repay(settle_amount)?;
+        }
+        Ok(())
+    }
+
+    /// Find collateral by deposit reserve
+    pub fn find_collateral_in_deposits(
+        &self,
+        deposit_reserve: Pubkey,
+    ) -> Result<(&ObligationCollateral, usize), ProgramError> {
+        if self.deposits.is_empty() {
+            msg!("Obligation has no deposits");
+            return Err(LendingError::ObligationDepositsEmpty.into());
+        }
+        let collateral_index = self
+            ._find_collateral_index_in_deposits(deposit_reserve)
+            .ok_or(LendingError::InvalidObligationCollateral)?;
+        Ok((&self.deposits[collateral_index], collateral_index))
+    }
+
+    /// Find or add collateral by deposit reserve
+    pub fn find_or_add_collateral_to_deposits(
+        &mut self,
+        deposit_reserve: Pubkey,
+    ) -> Result<&mut ObligationCollateral, ProgramError> {
+        if let Some(collateral_index) = self._find_collateral_index_in_deposits(deposit_reserve) {
+            return Ok(&mut self.deposits[collateral_index]);
+        }
+        if self.deposits.len() + self.borrows.len() >= MAX_OBLIGATION_RESERVES {
+            msg!(
+                "Obligation cannot have more than {} deposits and borrows combined",
+                MAX_OBLIGATION_RESERVES
+            );
+            return Err(LendingError::ObligationReserveLimit.into());
+        }
+        let collateral = ObligationCollateral::new(deposit_reserve);
+        self.deposits.push(collateral);
+        Ok(self.deposits.last_mut().unwrap())
+    }
+
+    fn _find_collateral_index_in_deposits(&self, deposit_reserve: Pubkey) -> Option<usize> {
+        self.deposits
+            .iter()
+            .position(|collateral| collateral.deposit_reserve == deposit_reserve)
+    }
+
+    /// Find liquidity by borrow reserve
+    pub fn find_liquidity_in_borrows(
+        &self,
+        borrow_reserve: Pubkey,
+    ) -> Result<(&ObligationLiquidity, usize), ProgramError> {
+        if self.borrows.is_empty() {
+            msg!("Obligation has no borrows");
+            return Err(LendingError::ObligationBorrowsEmpty.into());
+        }
+        let liquidity_index = self
+            ._find_liquidity_index_in_borrows(borrow_reserve)
+            .ok_or(LendingError::InvalidObligationLiquidity)?;
+        Ok((&self.borrows[liquidity_index], liquidity_index))
+    }
+
+    /// Find or add liquidity by borrow reserve
+    pub fn find_or_add_liquidity_to_borrows(
+        &mut self,
+        borrow_reserve: Pubkey,
+        cumulative_borrow_rate_wads: Decimal,
+    ) -> Result<&mut ObligationLiquidity, ProgramError> {
+        if let Some(liquidity_index) = self._find_liquidity_index_in_borrows(borrow_reserve) {
+            return Ok(&mut self.borrows[liquidity_index]);
+        }
+        if self.deposits.len() + self.borrows.len() >= MAX_OBLIGATION_RESERVES {
+            msg!(
+                "Obligation cannot have more than {} deposits and borrows combined",
+                MAX_OBLIGATION_RESERVES
+            );
+            return Err(LendingError::ObligationReserveLimit.into());
+        }
+        let liquidity = ObligationLiquidity::new(borrow_reserve, cumulative_borrow_rate_wads);
+        self.borrows.push(liquidity);
+        Ok(self.borrows.last_mut().unwrap())
+    }
+
+    fn _find_liquidity_index_in_borrows(&self, borrow_reserve: Pubkey) -> Option<usize> {
+        self.borrows
+            .iter()
+            .position(|liquidity| liquidity.borrow_reserve == borrow_reserve)
+    }
+}
+
+fn find_reserve<'a>(
+    reserves: &'a [(Pubkey, Reserve)],
+    reserve_pubkey: &Pubkey,
+) -> Result<&'a Reserve, ProgramError> {
+    reserves
+        .iter()
+        .find(|(pubkey, _)| pubkey == reserve_pubkey)
+        .map(|(_, reserve)| reserve)
+        .ok_or_else(|| {
+            msg!("Reserve {} of the obligation is missing", reserve_pubkey);
+            LendingError::InvalidAccountInput.into()
+        })
 }
 
 /// Initialize an obligation
@@ -101,6 +332,34 @@ pub struct ObligationCollateral {
     pub market_value: Decimal,
 }
 
+impl ObligationCollateral {
+    /// Create new obligation collateral
+    pub fn new(deposit_reserve: Pubkey) -> Self {
+        Self {
+            deposit_reserve,
+            ..Self::default()
+        }
+    }
+
+    /// Increase deposited collateral
+    pub fn deposit(&mut self, collateral_amount: u64) -> ProgramResult {
+        self.deposited_amount = self
+            .deposited_amount
+            .checked_add(collateral_amount)
+            .ok_or(LendingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Decrease deposited collateral
+    pub fn withdraw(&mut self, collateral_amount: u64) -> ProgramResult {
+        self.deposited_amount = self
+            .deposited_amount
+            .checked_sub(collateral_amount)
+            .ok_or(LendingError::MathOverflow)?;
+        Ok(())
+    }
+}
+
 /// Obligation liquidity state
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct ObligationLiquidity {
@@ -116,6 +375,52 @@ pub struct ObligationLiquidity {
     pub market_value: Decimal,
 }
 
+impl ObligationLiquidity {
+    /// Create new obligation liquidity
+    pub fn new(borrow_reserve: Pubkey, cumulative_borrow_rate_wads: Decimal) -> Self {
+        Self {
+            borrow_reserve,
+            cumulative_borrow_rate_wads,
+            ..Self::default()
+        }
+    }
+
+    /// Decrease borrowed liquidity
+    pub fn repay(&mut self, settle_amount: Decimal) -> ProgramResult {
+        self.borrowed_amount_wads = self.borrowed_amount_wads.try_sub(settle_amount)?;
+        Ok(())
+    }
+
+    /// Increase borrowed liquidity
+    pub fn borrow(&mut self, borrow_amount: Decimal) -> ProgramResult {
+        self.borrowed_amount_wads = self.borrowed_amount_wads.try_add(borrow_amount)?;
+        Ok(())
+    }
+
+    /// Accrue interest
+    pub fn accrue_interest(&mut self, cumulative_borrow_rate_wads: Decimal) -> ProgramResult {
+        match cumulative_borrow_rate_wads.cmp(&self.cumulative_borrow_rate_wads) {
+            Ordering::Less => {
+                msg!("Interest rate cannot be negative");
+                return Err(LendingError::NegativeInterestRate.into());
+            }
+            Ordering::Equal => {}
+            Ordering::Greater => {
+                let compounded_interest_rate: Rate = cumulative_borrow_rate_wads
+                    .try_div(self.cumulative_borrow_rate_wads)?
+                    .try_into()?;
+
+                self.borrowed_amount_wads = self
+                    .borrowed_amount_wads
+                    .try_mul(compounded_interest_rate)?;
+                self.cumulative_borrow_rate_wads = cumulative_borrow_rate_wads;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 
 const OBLIGATION_COLLATERAL_LEN: usize = 72; // 32 + 8 + 16 + 16
 const OBLIGATION_LIQUIDITY_LEN: usize = 96; // 32 + 16 + 16 + 16
@@ -318,7 +623,7 @@ impl Pack for Obligation {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::math::TryAdd;
+    use crate::math::WAD;
     use proptest::prelude::*;
 
     const MAX_COMPOUNDED_INTEREST: u64 = 100; // 10,000%
@@ -449,4 +754,219 @@ mod test {
             }
         }
     }
+
+    fn obligation_values(deposited: u64, borrowed: u64, allowed: u64, unhealthy: u64) -> Obligation {
+        Obligation {
+            deposited_value: Decimal::from(deposited),
+            borrowed_value: Decimal::from(borrowed),
+            allowed_borrow_value: Decimal::from(allowed),
+            unhealthy_borrow_value: Decimal::from(unhealthy),
+            ..Obligation::default()
+        }
+    }
+
+    #[test]
+    fn obligation_health() {
+        // 100 deposited at 75% LTV and 80% liquidation threshold, 60 borrowed
+        let obligation = obligation_values(100, 60, 75, 80);
+        assert_eq!(obligation.loan_to_value(), Ok(Some(Decimal::from_percent(60))));
+        assert_eq!(
+            obligation.health_factor(),
+            Decimal::from(4u64).try_div(Decimal::from(3u64)).map(Some)
+        );
+        assert!(!obligation.is_liquidatable());
+        assert_eq!(obligation.remaining_borrow_value(), Ok(Decimal::from(15u64)));
+        assert_eq!(obligation.max_withdraw_value(), Ok(Decimal::from(20u64)));
+
+        // exactly at the unhealthy borrow value
+        let obligation = obligation_values(100, 80, 75, 80);
+        assert_eq!(obligation.health_factor(), Ok(Some(Decimal::one())));
+        assert!(obligation.is_liquidatable());
+        assert_eq!(obligation.remaining_borrow_value(), Ok(Decimal::zero()));
+        assert_eq!(obligation.max_withdraw_value(), Ok(Decimal::zero()));
+
+        // nothing borrowed
+        let obligation = obligation_values(100, 0, 75, 80);
+        assert_eq!(obligation.health_factor(), Ok(None));
+        assert!(!obligation.is_liquidatable());
+        assert_eq!(obligation.max_withdraw_value(), Ok(Decimal::from(100u64)));
+        assert!(!Obligation::default().is_liquidatable());
+
+        // nothing deposited
+        assert_eq!(Obligation::default().loan_to_value(), Ok(None));
+    }
+
+    #[test]
+    fn obligation_liquidation_threshold() {
+        let mut obligation = obligation_values(100, 80, 75, 80);
+
+        // a billionth below the unhealthy borrow value
+        obligation.borrowed_value = Decimal::from(80u64)
+            .try_sub(Decimal::from_scaled_val(1_000_000_000))
+            .unwrap();
+        assert!(obligation.health_factor().unwrap().unwrap() > Decimal::one());
+        assert!(!obligation.is_liquidatable());
+
+        // a health factor of exactly 1 can be liquidated
+        obligation.borrowed_value = Decimal::from(80u64);
+        assert_eq!(obligation.health_factor(), Ok(Some(Decimal::one())));
+        assert!(obligation.is_liquidatable());
+
+        obligation.borrowed_value = Decimal::from(81u64);
+        assert!(obligation.health_factor().unwrap().unwrap() < Decimal::one());
+        assert!(obligation.is_liquidatable());
+    }
+
+    #[test]
+    fn obligation_refresh() {
+        use crate::state::reserve::{ReserveCollateral, ReserveConfig, ReserveLiquidity};
+
+        // 200 liquidity backs 100 collateral, worth 2 each
+        let deposit_reserve = Reserve {
+            liquidity: ReserveLiquidity {
+                mint_decimals: 6,
+                available_amount: 150_000_000,
+                borrowed_amount_wads: Decimal::from(60_000_000u64),
+                owner_unclaimed: Decimal::from(10_000_000u64),
+                market_price: Decimal::from(2u64),
+                ..ReserveLiquidity::default()
+            },
+            collateral: ReserveCollateral {
+                mint_total_supply: 100_000_000,
+                ..ReserveCollateral::default()
+            },
+            config: ReserveConfig {
+                loan_to_value_ratio: 50,
+                liquidation_threshold: 80,
+                ..ReserveConfig::default()
+            },
+            ..Reserve::default()
+        };
+        let borrow_reserve = Reserve {
+            liquidity: ReserveLiquidity {
+                mint_decimals: 9,
+                cumulative_borrow_rate_wads: Decimal::from_percent(110),
+                market_price: Decimal::one(),
+                ..ReserveLiquidity::default()
+            },
+            ..Reserve::default()
+        };
+        let reserves = vec![
+            (Pubkey::new_unique(), deposit_reserve),
+            (Pubkey::new_unique(), borrow_reserve),
+        ];
+
+        let mut obligation = Obligation {
+            deposits: vec![ObligationCollateral {
+                deposit_reserve: reserves[0].0,
+                deposited_amount: 50_000_000,
+                ..ObligationCollateral::default()
+            }],
+            borrows: vec![ObligationLiquidity {
+                borrow_reserve: reserves[1].0,
+                cumulative_borrow_rate_wads: Decimal::one(),
+                borrowed_amount_wads: Decimal::from(100_000_000_000u64),
+                ..ObligationLiquidity::default()
+            }],
+            ..Obligation::default()
+        };
+        obligation.refresh(&reserves).unwrap();
+
+        assert_eq!(obligation.deposits[0].market_value, Decimal::from(200u64));
+        assert_eq!(obligation.deposited_value, Decimal::from(200u64));
+        assert_eq!(obligation.allowed_borrow_value, Decimal::from(100u64));
+        assert_eq!(obligation.unhealthy_borrow_value, Decimal::from(160u64));
+        assert_eq!(
+            obligation.borrows[0].borrowed_amount_wads,
+            Decimal::from(110_000_000_000u64)
+        );
+        assert_eq!(
+            obligation.borrows[0].cumulative_borrow_rate_wads,
+            Decimal::from_percent(110)
+        );
+        assert_eq!(obligation.borrowed_value, Decimal::from(110u64));
+
+        assert_eq!(
+            obligation.refresh(&reserves[..1]),
+            Err(LendingError::InvalidAccountInput.into())
+        );
+    }
+
+    #[test]
+    fn obligation_positions() {
+        let mut obligation = Obligation::default();
+        let deposit_reserve = Pubkey::new_unique();
+        assert_eq!(
+            obligation.find_collateral_in_deposits(deposit_reserve),
+            Err(LendingError::ObligationDepositsEmpty.into())
+        );
+        assert_eq!(
+            obligation.find_liquidity_in_borrows(Pubkey::new_unique()),
+            Err(LendingError::ObligationBorrowsEmpty.into())
+        );
+
+        obligation
+            .find_or_add_collateral_to_deposits(deposit_reserve)
+            .unwrap()
+            .deposit(100)
+            .unwrap();
+        obligation
+            .find_or_add_collateral_to_deposits(deposit_reserve)
+            .unwrap()
+            .deposit(50)
+            .unwrap();
+        assert_eq!(obligation.deposits.len(), 1);
+        assert_eq!(obligation.deposits[0].deposited_amount, 150);
+        assert_eq!(
+            obligation.find_collateral_in_deposits(Pubkey::new_unique()),
+            Err(LendingError::InvalidObligationCollateral.into())
+        );
+        assert_eq!(
+            obligation.deposits[0].withdraw(151),
+            Err(LendingError::MathOverflow.into())
+        );
+        obligation.withdraw(50, 0).unwrap();
+        assert_eq!(obligation.deposits[0].deposited_amount, 100);
+
+        let borrow_reserve = Pubkey::new_unique();
+        let liquidity = obligation
+            .find_or_add_liquidity_to_borrows(borrow_reserve, Decimal::from_percent(120))
+            .unwrap();
+        liquidity.borrow(Decimal::from(10u64)).unwrap();
+        assert_eq!(
+            liquidity.cumulative_borrow_rate_wads,
+            Decimal::from_percent(120)
+        );
+        assert_eq!(
+            obligation.find_liquidity_in_borrows(borrow_reserve),
+            Ok((&obligation.borrows[0], 0))
+        );
+
+        for _ in 2..MAX_OBLIGATION_RESERVES {
+            obligation
+                .find_or_add_collateral_to_deposits(Pubkey::new_unique())
+                .unwrap();
+        }
+        assert_eq!(
+            obligation
+                .find_or_add_liquidity_to_borrows(Pubkey::new_unique(), Decimal::one())
+                .map(|_| ()),
+            Err(LendingError::ObligationReserveLimit.into())
+        );
+        assert_eq!(
+            obligation
+                .find_or_add_collateral_to_deposits(Pubkey::new_unique())
+                .map(|_| ()),
+            Err(LendingError::ObligationReserveLimit.into())
+        );
+        assert!(obligation
+            .find_or_add_collateral_to_deposits(deposit_reserve)
+            .is_ok());
+
+        obligation.withdraw(100, 0).unwrap();
+        assert_eq!(
+            obligation.find_collateral_in_deposits(deposit_reserve),
+            Err(LendingError::InvalidObligationCollateral.into())
+        );
+    }
 }
@@ -1,17 +1,21 @@
 use super::*;
 use crate::{
-    math::{Decimal, Rate},
+    error::LendingError,
+    math::{Decimal, Rate, TryAdd, TryDiv, TryMul, TrySub},
+    state::obligation::{Obligation, ObligationCollateral, ObligationLiquidity},
 };
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::{
     clock::Slot,
+    entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
     pubkey::{Pubkey, PUBKEY_BYTES},
 };
 use std::{
-    convert::{TryFrom},
+    cmp::Ordering,
+    convert::{TryFrom, TryInto},
 };
 use crate::state::last_update::LastUpdate;
 
@@ -79,6 +83,329 @@ pub struct Reserve {
 
 }
 
+impl Reserve {
+    /// Collateral exchange rate
+    pub fn collateral_exchange_rate(&self) -> Result<CollateralExchangeRate, ProgramError> {
+        let total_liquidity = self.liquidity.total_supply()?;
+        self.collateral.exchange_rate(total_liquidity)
+    }
+
+    /// Calculate the current utilization rate of the reserve
+    pub fn utilization_rate(&self) -> Result<Rate, ProgramError> {
+        self.liquidity.utilization_rate()
+    }
+
+    /// Calculate the current borrow rate.
+    ///
+    /// The rate grows linearly from `min_borrow_rate` to `optimal_borrow_rate` until the
+    /// utilization reaches the kink, then linearly to `max_borrow_rate` at full utilization.
+    pub fn current_borrow_rate(&self) -> Result<Rate, ProgramError> {
+        let utilization_rate = self.utilization_rate()?;
+        let kink_utilization_rate = self.config.kink_utilization_rate();
+        let kink_rate = Rate::from_percent(kink_utilization_rate);
+        let low_utilization = utilization_rate < kink_rate;
+        if low_utilization || kink_utilization_rate == 100 {
+            let normalized_rate = utilization_rate.try_div(kink_rate)?;
+            let min_rate = Rate::from_percent(self.config.min_borrow_rate);
+            let rate_range = Rate::from_percent(
+                self.config
+                    .optimal_borrow_rate
+                    .checked_sub(self.config.min_borrow_rate)
+                    .ok_or(LendingError::MathOverflow)?,
+            );
+
+            Ok(normalized_rate.try_mul(rate_range)?.try_add(min_rate)?)
+        } else {
+            let normalized_rate = utilization_rate
+                .try_sub(kink_rate)?
+                .try_div(Rate::from_percent(
+                    100u8
+                        .checked_sub(kink_utilization_rate)
+                        .ok_or(LendingError::MathOverflow)?,
+                ))?;
+            let min_rate = Rate::from_percent(self.config.optimal_borrow_rate);
+            let rate_range = Rate::from_percent(
+                self.config
+                    .max_borrow_rate
+                    .checked_sub(self.config.optimal_borrow_rate)
+                    .ok_or(LendingError::MathOverflow)?,
+            );
+
+            Ok(normalized_rate.try_mul(rate_range)?.try_add(min_rate)?)
+        }
+    }
+
+    /// Calculate the current supply rate: the borrow interest earned by the supplied liquidity,
+    /// net of the reserve owner fee
+    pub fn current_supply_rate(&self) -> Result<Rate, ProgramError> {
+        let reserve_owner_fee_rate = Rate::from_scaled_val(self.config.fees.reserve_owner_fee_wad);
+        self.current_borrow_rate()?
+            .try_mul(self.utilization_rate()?)?
+            .try_mul(Rate::one().try_sub(reserve_owner_fee_rate)?)
+    }
+
+    /// Calculate the current borrow APY, compounded every slot
+    pub fn current_borrow_apy(&self) -> Result<Rate, ProgramError> {
+        apr_to_apy(self.current_borrow_rate()?)
+    }
+
+    /// Calculate the current supply APY, compounded every slot
+    pub fn current_supply_apy(&self) -> Result<Rate, ProgramError> {
+        apr_to_apy(self.current_supply_rate()?)
+    }
+
+    /// Compound interest of the slots elapsed since the last update at the current borrow rate
+    pub fn accrue_interest(&mut self, current_slot: Slot) -> ProgramResult {
+        let slots_elapsed = self.last_update.slots_elapsed(current_slot)?;
+        if slots_elapsed > 0 {
+            let current_borrow_rate = self.current_borrow_rate()?;
+            let reserve_owner_fee_rate =
+                Rate::from_scaled_val(self.config.fees.reserve_owner_fee_wad);
+            self.liquidity.compound_interest(
+                current_borrow_rate,
+                slots_elapsed,
+                reserve_owner_fee_rate,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Calculate the share of the mining speed distributed to borrowers: the utilization rate,
+    /// capped at the `Bonus::supply_rate` kink. Suppliers are distributed the rest
+    pub fn borrow_mining_rate(&self) -> Result<Rate, ProgramError> {
+        Ok(self.utilization_rate()?.min(self.bonus.kink_util_rate()))
+    }
+
+    /// Advance the mining indices by the mine distributed over the slots elapsed since the last
+    /// update. Mine of a side without LTokens or borrows is not distributed
+    pub fn accrue_mining_index(&mut self, current_slot: Slot) -> ProgramResult {
+        let slots_elapsed = self.last_update.slots_elapsed(current_slot)?;
+        if slots_elapsed == 0 || self.bonus.total_mining_speed == 0 {
+            return Ok(());
+        }
+        let mine = Decimal::from(self.bonus.total_mining_speed).try_mul(slots_elapsed)?;
+        let borrow_mining_rate = self.borrow_mining_rate()?;
+        let borrow_mine = mine.try_mul(borrow_mining_rate)?;
+        let l_token_mine = mine.try_mul(Rate::one().try_sub(borrow_mining_rate)?)?;
+
+        if self.collateral.mint_total_supply > 0 {
+            self.bonus.l_token_mining_index = self
+                .bonus
+                .l_token_mining_index
+                .try_add(l_token_mine.try_div(self.collateral.mint_total_supply)?)?;
+        }
+        if self.liquidity.borrowed_amount_wads > Decimal::zero() {
+            self.bonus.borrow_mining_index = self
+                .bonus
+                .borrow_mining_index
+                .try_add(borrow_mine.try_div(self.liquidity.borrowed_amount_wads)?)?;
+        }
+        Ok(())
+    }
+
+    /// Project the reserve to `slot` by accruing interest on a copy, leaving the market price,
+    /// staleness and mining indexes untouched
+    pub fn project_to_slot(&self, slot: Slot) -> Result<Reserve, ProgramError> {
+        let mut reserve = self.clone();
+        reserve.accrue_interest(slot)?;
+        reserve.last_update.slot = slot;
+        Ok(reserve)
+    }
+
+    /// Calculate borrow amount and fees, `u64::MAX` borrows up to `max_borrow_value`
+    pub fn calculate_borrow(
+        &self,
+        amount_to_borrow: u64,
+        max_borrow_value: Decimal,
+    ) -> Result<CalculateBorrowResult, ProgramError> {
+        let decimals = 10u64
+            .checked_pow(self.liquidity.mint_decimals as u32)
+            .ok_or(LendingError::MathOverflow)?;
+        if amount_to_borrow == u64::MAX {
+            let borrow_amount = max_borrow_value
+                .try_mul(decimals)?
+                .try_div(self.liquidity.market_price)?
+                .min(self.liquidity.available_amount.into());
+            let (borrow_fee, host_fee) = self
+                .config
+                .fees
+                .calculate_borrow_fees(borrow_amount, FeeCalculation::Inclusive)?;
+            let receive_amount = borrow_amount
+                .try_floor_u64()?
+                .checked_sub(borrow_fee)
+                .ok_or(LendingError::MathOverflow)?;
+            if receive_amount == 0 {
+                msg!("Borrow amount is too small to receive liquidity after fees");
+                return Err(LendingError::BorrowTooSmall.into());
+            }
+
+            Ok(CalculateBorrowResult {
+                borrow_amount,
+                receive_amount,
+                borrow_fee,
+                host_fee,
+            })
+        } else {
+            let receive_amount = amount_to_borrow;
+            if receive_amount == 0 {
+                msg!("Borrow amount is too small to receive liquidity after fees");
+                return Err(LendingError::BorrowTooSmall.into());
+            }
+            let borrow_amount = Decimal::from(receive_amount);
+            let (borrow_fee, host_fee) = self
+                .config
+                .fees
+                .calculate_borrow_fees(borrow_amount, FeeCalculation::Exclusive)?;
+
+            let borrow_amount = borrow_amount.try_add(borrow_fee.into())?;
+            let borrow_value = borrow_amount
+                .try_mul(self.liquidity.market_price)?
+                .try_div(decimals)?;
+            if borrow_value > max_borrow_value {
+                msg!("Borrow value cannot exceed maximum borrow value");
+                return Err(LendingError::BorrowTooLarge.into());
+            }
+
+            Ok(CalculateBorrowResult {
+                borrow_amount,
+                receive_amount,
+                borrow_fee,
+                host_fee,
+            })
+        }
+    }
+
+    /// Calculate repay amount, `u64::MAX` repays the whole borrowed amount
+    pub fn calculate_repay(
+        &self,
+        amount_to_repay: u64,
+        borrowed_amount: Decimal,
+    ) -> Result<CalculateRepayResult, ProgramError> {
+        let settle_amount = if amount_to_repay == u64::MAX {
+            borrowed_amount
+        } else {
+            Decimal::from(amount_to_repay).min(borrowed_amount)
+        };
+        let repay_amount = settle_amount.try_ceil_u64()?;
+        if repay_amount == 0 {
+            msg!("Repay amount is too small to transfer liquidity");
+            return Err(LendingError::RepayTooSmall.into());
+        }
+
+        Ok(CalculateRepayResult {
+            settle_amount,
+            repay_amount,
+        })
+    }
+
+    /// Calculate liquidation amounts of an obligation's collateral deposited in this reserve.
+    ///
+    /// `amount_to_liquidate` is capped by `LIQUIDATION_CLOSE_FACTOR` of the obligation's
+    /// borrowed value, `u64::MAX` liquidates as much as allowed. Borrows smaller than
+    /// `LIQUIDATION_CLOSE_AMOUNT` are closed out at once. The collateral withdrawn includes the
+    /// reserve's `liquidation_bonus`.
+    pub fn calculate_liquidation(
+        &self,
+        amount_to_liquidate: u64,
+        obligation: &Obligation,
+        liquidity: &ObligationLiquidity,
+        collateral: &ObligationCollateral,
+    ) -> Result<CalculateLiquidationResult, ProgramError> {
+        let bonus_rate = Rate::from_percent(self.config.liquidation_bonus).try_add(Rate::one())?;
+
+        let max_amount = if amount_to_liquidate == u64::MAX {
+            liquidity.borrowed_amount_wads
+        } else {
+            Decimal::from(amount_to_liquidate).min(liquidity.borrowed_amount_wads)
+        };
+
+        let settle_amount;
+        let repay_amount;
+        let withdraw_amount;
+
+        // Close out obligations that are too small to liquidate normally
+        if liquidity.borrowed_amount_wads < LIQUIDATION_CLOSE_AMOUNT.into() {
+            // settle_amount is fixed, calculate withdraw_amount and repay_amount
+            settle_amount = liquidity.borrowed_amount_wads;
+
+            let liquidation_value = liquidity.market_value.try_mul(bonus_rate)?;
+            match liquidation_value.cmp(&collateral.market_value) {
+                Ordering::Greater => {
+                    let repay_pct = collateral.market_value.try_div(liquidation_value)?;
+                    repay_amount = max_amount.try_mul(repay_pct)?.try_ceil_u64()?;
+                    withdraw_amount = collateral.deposited_amount;
+                }
+                Ordering::Equal => {
+                    repay_amount = max_amount.try_ceil_u64()?;
+                    withdraw_amount = collateral.deposited_amount;
+                }
+                Ordering::Less => {
+                    let withdraw_pct = liquidation_value.try_div(collateral.market_value)?;
+                    repay_amount = max_amount.try_floor_u64()?;
+                    withdraw_amount = Decimal::from(collateral.deposited_amount)
+                        .try_mul(withdraw_pct)?
+                        .try_floor_u64()?;
+                }
+            }
+        } else {
+            // calculate settle_amount and withdraw_amount, repay_amount is settle_amount rounded
+            let liquidation_amount = obligation
+                .max_liquidation_amount(liquidity)?
+                .min(max_amount);
+            let liquidation_pct = liquidation_amount.try_div(liquidity.borrowed_amount_wads)?;
+            let liquidation_value = liquidity
+                .market_value
+                .try_mul(liquidation_pct)?
+                .try_mul(bonus_rate)?;
+
+            match liquidation_value.cmp(&collateral.market_value) {
+                Ordering::Greater => {
+                    let repay_pct = collateral.market_value.try_div(liquidation_value)?;
+                    settle_amount = liquidation_amount.try_mul(repay_pct)?;
+                    repay_amount = settle_amount.try_ceil_u64()?;
+                    withdraw_amount = collateral.deposited_amount;
+                }
+                Ordering::Equal => {
+                    settle_amount = liquidation_amount;
+                    repay_amount = settle_amount.try_ceil_u64()?;
+                    withdraw_amount = collateral.deposited_amount;
+                }
+                Ordering::Less => {
+                    let withdraw_pct = liquidation_value.try_div(collateral.market_value)?;
+                    settle_amount = liquidation_amount;
+                    repay_amount = settle_amount.try_floor_u64()?;
+                    withdraw_amount = Decimal::from(collateral.deposited_amount)
+                        .try_mul(withdraw_pct)?
+                        .try_floor_u64()?;
+                }
+            }
+        }
+
+        if repay_amount == 0 {
+            msg!("Liquidation is too small to transfer liquidity");
+            return Err(LendingError::LiquidationTooSmall.into());
+        }
+        if withdraw_amount == 0 {
+            msg!("Liquidation is too small to receive collateral");
+            return Err(LendingError::LiquidationTooSmall.into());
+        }
+
+        Ok(CalculateLiquidationResult {
+            settle_amount,
+            repay_amount,
+            withdraw_amount,
+        })
+    }
+}
+
+
+/// Convert an annual rate into the annual yield of compounding it every slot
+pub fn apr_to_apy(apr: Rate) -> Result<Rate, ProgramError> {
+    Rate::one()
+        .try_add(apr.try_div(SLOTS_PER_YEAR)?)?
+        .try_pow(SLOTS_PER_YEAR)?
+        .try_sub(Rate::one())
+}
 
 /// Calculate borrow result
 #[derive(Debug)]
@@ -147,6 +474,70 @@ pub struct ReserveLiquidity {
     pub owner_unclaimed: Decimal
 }
 
+impl ReserveLiquidity {
+    /// Oracle account passed with the reserve to `RefreshReserves`: the LpPrice account when
+    /// the liquidity is a lp, otherwise the pyth price or larix oracle account
+    pub fn oracle_pubkey(&self) -> Pubkey {
+        if self.is_lp || !self.use_pyth_oracle {
+            self.params_2
+        } else {
+            self.params_1
+        }
+    }
+
+    /// Calculate the total reserve supply including active loans, excluding the fees owed to
+    /// the reserve owner
+    pub fn total_supply(&self) -> Result<Decimal, ProgramError> {
+        Decimal::from(self.available_amount)
+            .try_add(self.borrowed_amount_wads)?
+            .try_sub(self.owner_unclaimed)
+    }
+
+    /// Calculate the market value of a liquidity amount in quote currency
+    pub fn market_value(&self, liquidity_amount: Decimal) -> Result<Decimal, ProgramError> {
+        let decimals = 10u64
+            .checked_pow(self.mint_decimals as u32)
+            .ok_or(LendingError::MathOverflow)?;
+        liquidity_amount
+            .try_mul(self.market_price)?
+            .try_div(decimals)
+    }
+
+    /// Compound current borrow rate over elapsed slots, the reserve owner takes their fee of
+    /// the interest
+    fn compound_interest(
+        &mut self,
+        current_borrow_rate: Rate,
+        slots_elapsed: u64,
+        reserve_owner_fee_rate: Rate,
+    ) -> ProgramResult {
+        let slot_interest_rate = current_borrow_rate.try_div(SLOTS_PER_YEAR)?;
+        let compounded_interest_rate = Rate::one()
+            .try_add(slot_interest_rate)?
+            .try_pow(slots_elapsed)?;
+        self.cumulative_borrow_rate_wads = self
+            .cumulative_borrow_rate_wads
+            .try_mul(compounded_interest_rate)?;
+
+        let borrowed_amount_wads = self.borrowed_amount_wads.try_mul(compounded_interest_rate)?;
+        let interest = borrowed_amount_wads.try_sub(self.borrowed_amount_wads)?;
+        self.owner_unclaimed = self
+            .owner_unclaimed
+            .try_add(interest.try_mul(reserve_owner_fee_rate)?)?;
+        self.borrowed_amount_wads = borrowed_amount_wads;
+        Ok(())
+    }
+
+    /// Calculate the liquidity utilization rate of the reserve
+    pub fn utilization_rate(&self) -> Result<Rate, ProgramError> {
+        let total_supply = self.total_supply()?;
+        if total_supply == Decimal::zero() {
+            return Ok(Rate::zero());
+        }
+        self.borrowed_amount_wads.try_div(total_supply)?.try_into()
+    }
+}
+
 
 /// Create a new reserve liquidity
 pub struct NewReserveLiquidityParams {
@@ -192,10 +583,69 @@ pub struct NewReserveCollateralParams {
     pub supply_pubkey: Pubkey,
 }
 
+impl ReserveCollateral {
+    /// Return the current collateral exchange rate.
+    pub fn exchange_rate(
+        &self,
+        total_liquidity: Decimal,
+    ) -> Result<CollateralExchangeRate, ProgramError> {
+        let rate = if self.mint_total_supply == 0 || total_liquidity == Decimal::zero() {
+            Rate::from_scaled_val(INITIAL_COLLATERAL_RATE)
+        } else {
+            let mint_total_supply = Decimal::from(self.mint_total_supply);
+            Rate::try_from(mint_total_supply.try_div(total_liquidity)?)?
+        };
+
+        Ok(CollateralExchangeRate(rate))
+    }
+}
+
 /// Collateral exchange rate
 #[derive(Clone, Copy, Debug)]
 pub struct CollateralExchangeRate(Rate);
 
+impl CollateralExchangeRate {
+    /// Convert reserve collateral to liquidity, rounded down
+    pub fn collateral_to_liquidity(&self, collateral_amount: u64) -> Result<u64, ProgramError> {
+        self.decimal_collateral_to_liquidity(collateral_amount.into())?
+            .try_floor_u64()
+    }
+
+    /// Convert reserve collateral to liquidity, rounded up
+    pub fn collateral_to_liquidity_ceil(&self, collateral_amount: u64) -> Result<u64, ProgramError> {
+        self.decimal_collateral_to_liquidity(collateral_amount.into())?
+            .try_ceil_u64()
+    }
+
+    /// Convert reserve collateral to liquidity
+    pub fn decimal_collateral_to_liquidity(
+        &self,
+        collateral_amount: Decimal,
+    ) -> Result<Decimal, ProgramError> {
+        collateral_amount.try_div(self.0)
+    }
+
+    /// Convert reserve liquidity to collateral, rounded down
+    pub fn liquidity_to_collateral(&self, liquidity_amount: u64) -> Result<u64, ProgramError> {
+        self.decimal_liquidity_to_collateral(liquidity_amount.into())?
+            .try_floor_u64()
+    }
+
+    /// Convert reserve liquidity to collateral, rounded up
+    pub fn liquidity_to_collateral_ceil(&self, liquidity_amount: u64) -> Result<u64, ProgramError> {
+        self.decimal_liquidity_to_collateral(liquidity_amount.into())?
+            .try_ceil_u64()
+    }
+
+    /// Convert reserve liquidity to collateral
+    pub fn decimal_liquidity_to_collateral(
+        &self,
+        liquidity_amount: Decimal,
+    ) -> Result<Decimal, ProgramError> {
+        liquidity_amount.try_mul(self.0)
+    }
+}
+
 impl From<CollateralExchangeRate> for Rate {
     fn from(exchange_rate: CollateralExchangeRate) -> Self {
         exchange_rate.0
@@ -212,7 +662,8 @@ pub struct Bonus {
 
     /// Amount of mine token for this reserve per slot
     pub total_mining_speed: u64,
-    /// the critical liquidity utilization rate at which the mine distribution curve jumps
+    /// the critical liquidity utilization rate at which the mine distribution curve jumps,
+    /// the `kink_util_rate` of `InitReserve` and the reserve configs
     pub supply_rate: u64,
 }
 pub struct InitBonusParams {
@@ -231,6 +682,12 @@ impl Bonus {
             supply_rate: params.supply_rate,
         }
     }
+
+    /// Utilization rate at which the borrowers' share of mine stops growing: `supply_rate`, a
+    /// percentage, as a rate. Values over 100 never kink
+    pub fn kink_util_rate(&self) -> Rate {
+        Rate::from_percent(self.supply_rate.min(100) as u8)
+    }
 }
 
 /// Initialize a reserve
@@ -277,6 +734,21 @@ pub struct ReserveConfig {
     pub liquidation_paused:bool,
     /// Deposit limit
     pub deposit_limit:u64,
+    /// Utilization rate at which the borrow rate curve kinks, as a percentage, 0 to kink at
+    /// `optimal_utilization_rate`. Only set from the deployment configs: the reserve account
+    /// does not store it and `InitReserve` sends it as its own `kink_util_rate`
+    pub kink_util_rate: u64,
+}
+
+impl ReserveConfig {
+    /// Utilization rate at which the borrow rate curve kinks, as a percentage. A
+    /// `kink_util_rate` over 100 kinks at full utilization
+    pub fn kink_utilization_rate(&self) -> u8 {
+        match self.kink_util_rate {
+            0 => self.optimal_utilization_rate,
+            kink_util_rate => kink_util_rate.min(100) as u8,
+        }
+    }
 }
 
 /// Additional fee information on a reserve
@@ -302,6 +774,79 @@ pub struct ReserveFees {
     /// Host fee receiver register
     pub host_fee_receivers:Vec<Pubkey>,
 }
+
+impl ReserveFees {
+    /// Calculate the owner and host fees on borrow
+    pub fn calculate_borrow_fees(
+        &self,
+        borrow_amount: Decimal,
+        fee_calculation: FeeCalculation,
+    ) -> Result<(u64, u64), ProgramError> {
+        self.calculate_fees(borrow_amount, self.borrow_fee_wad, fee_calculation)
+    }
+
+    /// Calculate the owner and host fees on flash loan
+    pub fn calculate_flash_loan_fees(
+        &self,
+        flash_loan_amount: Decimal,
+    ) -> Result<(u64, u64), ProgramError> {
+        self.calculate_fees(
+            flash_loan_amount,
+            self.flash_loan_fee_wad,
+            FeeCalculation::Exclusive,
+        )
+    }
+
+    /// Calculate the owner and host fees on an amount
+    fn calculate_fees(
+        &self,
+        amount: Decimal,
+        fee_wad: u64,
+        fee_calculation: FeeCalculation,
+    ) -> Result<(u64, u64), ProgramError> {
+        let fee_rate = Rate::from_scaled_val(fee_wad);
+        let host_fee_rate = Rate::from_percent(self.host_fee_percentage);
+        if fee_rate > Rate::zero() && amount > Decimal::zero() {
+            let need_to_assess_host_fee = host_fee_rate > Rate::zero();
+            let minimum_fee = if need_to_assess_host_fee {
+                2u64 // 1 token to owner, 1 to host
+            } else {
+                1u64 // 1 token to owner, nothing else
+            };
+
+            let fee_amount = match fee_calculation {
+                // Calculate fee to be added to amount: fee = amount * rate
+                FeeCalculation::Exclusive => amount.try_mul(fee_rate)?,
+                // Calculate fee to be subtracted from amount: fee = amount * (rate / (rate + 1))
+                FeeCalculation::Inclusive => {
+                    let fee_rate = fee_rate.try_div(fee_rate.try_add(Rate::one())?)?;
+                    amount.try_mul(fee_rate)?
+                }
+            };
+
+            let fee_decimal = fee_amount.max(minimum_fee.into());
+            if fee_decimal >= amount {
+                msg!("Borrow amount is too small to receive liquidity after fees");
+                return Err(LendingError::BorrowTooSmall.into());
+            }
+
+            let fee = fee_decimal.try_round_u64()?;
+            let host_fee = if need_to_assess_host_fee {
+                fee_decimal
+                    .try_mul(host_fee_rate)?
+                    .try_round_u64()?
+                    .max(1u64)
+            } else {
+                0
+            };
+
+            Ok((fee, host_fee))
+        } else {
+            Ok((0, 0))
+        }
+    }
+}
+
 /// Calculate fees exlusive or inclusive of an amount
 pub enum FeeCalculation {
     /// Fee added to amount: fee = rate * amount
@@ -653,6 +1198,7 @@ impl Pack for Reserve {
                 borrow_paused:unpack_bool(borrow_paused)?,
                 liquidation_paused:unpack_bool(liquidation_paused)?,
                 deposit_limit:u64::from_le_bytes(*deposit_limit),
+                kink_util_rate: 0,
             },
             bonus: Bonus{
                 un_coll_supply_account: Pubkey::new_from_array(*un_coll_supply_account),
@@ -665,3 +1211,385 @@ impl Pack for Reserve {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn collateral_exchange_rate() {
+        let mut reserve = Reserve::default();
+        let exchange_rate = reserve.collateral_exchange_rate().unwrap();
+        assert_eq!(
+            Rate::from(exchange_rate),
+            Rate::from_scaled_val(INITIAL_COLLATERAL_RATE)
+        );
+
+        // 500 liquidity, 10 of which belongs to the reserve owner, backs 200 collateral
+        reserve.liquidity.available_amount = 410;
+        reserve.liquidity.borrowed_amount_wads = Decimal::from(100u64);
+        reserve.liquidity.owner_unclaimed = Decimal::from(10u64);
+        reserve.collateral.mint_total_supply = 200;
+        let exchange_rate = reserve.collateral_exchange_rate().unwrap();
+
+        assert_eq!(exchange_rate.collateral_to_liquidity(200), Ok(500));
+        assert_eq!(exchange_rate.liquidity_to_collateral(500), Ok(200));
+        assert_eq!(exchange_rate.collateral_to_liquidity(1), Ok(2));
+        assert_eq!(exchange_rate.collateral_to_liquidity_ceil(1), Ok(3));
+        assert_eq!(exchange_rate.liquidity_to_collateral(1), Ok(0));
+        assert_eq!(exchange_rate.liquidity_to_collateral_ceil(1), Ok(1));
+        assert_eq!(exchange_rate.liquidity_to_collateral_ceil(5), Ok(2));
+    }
+
+    fn borrow_rate_reserve(available_amount: u64, borrowed_amount: u64) -> Reserve {
+        Reserve {
+            liquidity: ReserveLiquidity {
+                available_amount,
+                borrowed_amount_wads: Decimal::from(borrowed_amount),
+                ..ReserveLiquidity::default()
+            },
+            config: ReserveConfig {
+                optimal_utilization_rate: 80,
+                min_borrow_rate: 2,
+                optimal_borrow_rate: 22,
+                max_borrow_rate: 102,
+                ..ReserveConfig::default()
+            },
+            ..Reserve::default()
+        }
+    }
+
+    #[test]
+    fn current_borrow_rate() {
+        let reserve = borrow_rate_reserve(0, 0);
+        assert_eq!(reserve.utilization_rate(), Ok(Rate::zero()));
+        assert_eq!(reserve.current_borrow_rate(), Ok(Rate::from_percent(2)));
+
+        // halfway to the optimal utilization rate
+        let reserve = borrow_rate_reserve(60, 40);
+        assert_eq!(reserve.utilization_rate(), Ok(Rate::from_percent(40)));
+        assert_eq!(reserve.current_borrow_rate(), Ok(Rate::from_percent(12)));
+
+        let reserve = borrow_rate_reserve(20, 80);
+        assert_eq!(reserve.current_borrow_rate(), Ok(Rate::from_percent(22)));
+
+        // halfway past the optimal utilization rate
+        let reserve = borrow_rate_reserve(10, 90);
+        assert_eq!(reserve.current_borrow_rate(), Ok(Rate::from_percent(62)));
+
+        let reserve = borrow_rate_reserve(0, 100);
+        assert_eq!(reserve.utilization_rate(), Ok(Rate::one()));
+        assert_eq!(reserve.current_borrow_rate(), Ok(Rate::from_percent(102)));
+
+        // a kink at 50% utilization takes the place of the optimal utilization rate
+        let mut reserve = borrow_rate_reserve(60, 40);
+        reserve.config.kink_util_rate = 50;
+        assert_eq!(reserve.current_borrow_rate(), Ok(Rate::from_percent(18)));
+        reserve.liquidity.available_amount = 25;
+        reserve.liquidity.borrowed_amount_wads = Decimal::from(75u64);
+        assert_eq!(reserve.current_borrow_rate(), Ok(Rate::from_percent(62)));
+        reserve.config.kink_util_rate = 5000;
+        assert_eq!(reserve.config.kink_utilization_rate(), 100);
+    }
+
+    #[test]
+    fn current_supply_rate() {
+        let mut reserve = borrow_rate_reserve(60, 40);
+        reserve.config.fees.reserve_owner_fee_wad = 200_000_000_000_000_000;
+        // 12% borrow rate at 40% utilization, 20% of it kept by the reserve owner
+        assert_eq!(
+            reserve.current_supply_rate(),
+            Ok(Rate::from_scaled_val(38_400_000_000_000_000))
+        );
+
+        let apy = reserve.current_supply_apy().unwrap();
+        assert!(apy > Rate::from_scaled_val(39_146_000_000_000_000));
+        assert!(apy < Rate::from_scaled_val(39_147_000_000_000_000));
+        assert!(reserve.current_borrow_apy().unwrap() > reserve.current_borrow_rate().unwrap());
+
+        assert_eq!(borrow_rate_reserve(0, 0).current_supply_apy(), Ok(Rate::zero()));
+    }
+
+    #[test]
+    fn project_to_slot() {
+        let mut reserve = borrow_rate_reserve(60, 40);
+        reserve.liquidity.cumulative_borrow_rate_wads = Decimal::one();
+        reserve.config.fees.reserve_owner_fee_wad = 200_000_000_000_000_000;
+        reserve.last_update = LastUpdate::new(100);
+
+        assert_eq!(reserve.project_to_slot(100), Ok(reserve.clone()));
+        assert_eq!(
+            reserve.project_to_slot(99),
+            Err(LendingError::MathOverflow.into())
+        );
+
+        // a year at the 12% borrow rate
+        let projected = reserve.project_to_slot(100 + SLOTS_PER_YEAR).unwrap();
+        assert_eq!(projected.last_update.slot, 100 + SLOTS_PER_YEAR);
+        assert!(projected.last_update.stale);
+        let compounded_interest_rate = apr_to_apy(Rate::from_percent(12))
+            .unwrap()
+            .try_add(Rate::one())
+            .unwrap();
+        assert_eq!(
+            projected.liquidity.cumulative_borrow_rate_wads,
+            Decimal::from(compounded_interest_rate)
+        );
+        let interest = Decimal::from(40u64)
+            .try_mul(compounded_interest_rate)
+            .unwrap()
+            .try_sub(Decimal::from(40u64))
+            .unwrap();
+        assert_eq!(
+            projected.liquidity.borrowed_amount_wads,
+            Decimal::from(40u64).try_add(interest).unwrap()
+        );
+        assert_eq!(
+            projected.liquidity.owner_unclaimed,
+            interest.try_mul(Rate::from_percent(20)).unwrap()
+        );
+        assert_eq!(reserve.liquidity.borrowed_amount_wads, Decimal::from(40u64));
+    }
+
+    #[test]
+    fn calculate_borrow() {
+        let reserve = Reserve {
+            liquidity: ReserveLiquidity {
+                available_amount: 1_000,
+                market_price: Decimal::from(2u64),
+                ..ReserveLiquidity::default()
+            },
+            config: ReserveConfig {
+                fees: ReserveFees {
+                    borrow_fee_wad: 10_000_000_000_000_000, // 1%
+                    host_fee_percentage: 20,
+                    ..ReserveFees::default()
+                },
+                ..ReserveConfig::default()
+            },
+            ..Reserve::default()
+        };
+        let max_borrow_value = Decimal::from(300u64);
+
+        let result = reserve.calculate_borrow(100, max_borrow_value).unwrap();
+        assert_eq!(result.borrow_amount, Decimal::from(102u64));
+        assert_eq!(result.receive_amount, 100);
+        assert_eq!(result.borrow_fee, 2);
+        assert_eq!(result.host_fee, 1);
+
+        let result = reserve.calculate_borrow(u64::MAX, max_borrow_value).unwrap();
+        assert_eq!(result.borrow_amount, Decimal::from(150u64));
+        assert_eq!(result.receive_amount, 148);
+        assert_eq!(result.borrow_fee, 2);
+
+        assert_eq!(
+            reserve.calculate_borrow(200, max_borrow_value).map(|_| ()),
+            Err(LendingError::BorrowTooLarge.into())
+        );
+        assert_eq!(
+            reserve.calculate_borrow(0, max_borrow_value).map(|_| ()),
+            Err(LendingError::BorrowTooSmall.into())
+        );
+    }
+
+    #[test]
+    fn calculate_repay() {
+        let reserve = Reserve::default();
+        let borrowed_amount = Decimal::from(21u64).try_div(2u64).unwrap();
+
+        let result = reserve.calculate_repay(u64::MAX, borrowed_amount).unwrap();
+        assert_eq!(result.settle_amount, borrowed_amount);
+        assert_eq!(result.repay_amount, 11);
+
+        let result = reserve.calculate_repay(5, borrowed_amount).unwrap();
+        assert_eq!(result.settle_amount, Decimal::from(5u64));
+        assert_eq!(result.repay_amount, 5);
+
+        assert_eq!(
+            reserve.calculate_repay(u64::MAX, Decimal::zero()).map(|_| ()),
+            Err(LendingError::RepayTooSmall.into())
+        );
+    }
+
+    #[test]
+    fn calculate_liquidation() {
+        let reserve = Reserve {
+            config: ReserveConfig {
+                liquidation_bonus: 5,
+                ..ReserveConfig::default()
+            },
+            ..Reserve::default()
+        };
+        let obligation = Obligation {
+            borrowed_value: Decimal::from(100u64),
+            ..Obligation::default()
+        };
+        let liquidity = ObligationLiquidity {
+            borrowed_amount_wads: Decimal::from(100u64),
+            market_value: Decimal::from(100u64),
+            ..ObligationLiquidity::default()
+        };
+        let mut collateral = ObligationCollateral {
+            deposited_amount: 1_000,
+            market_value: Decimal::from(200u64),
+            ..ObligationCollateral::default()
+        };
+
+        // close factor caps the liquidation at half of the borrow
+        let result = reserve
+            .calculate_liquidation(u64::MAX, &obligation, &liquidity, &collateral)
+            .unwrap();
+        assert_eq!(result.settle_amount, Decimal::from(50u64));
+        assert_eq!(result.repay_amount, 50);
+        assert_eq!(result.withdraw_amount, 262);
+
+        let result = reserve
+            .calculate_liquidation(10, &obligation, &liquidity, &collateral)
+            .unwrap();
+        assert_eq!(result.repay_amount, 10);
+        assert_eq!(result.withdraw_amount, 52);
+
+        // not enough collateral to pay the bonus
+        collateral.market_value = Decimal::from(40u64);
+        let result = reserve
+            .calculate_liquidation(u64::MAX, &obligation, &liquidity, &collateral)
+            .unwrap();
+        assert_eq!(result.repay_amount, 39);
+        assert_eq!(result.withdraw_amount, 1_000);
+
+        // small borrows are closed out at once
+        let liquidity = ObligationLiquidity {
+            borrowed_amount_wads: Decimal::one(),
+            market_value: Decimal::one(),
+            ..ObligationLiquidity::default()
+        };
+        collateral.market_value = Decimal::from(200u64);
+        let result = reserve
+            .calculate_liquidation(u64::MAX, &obligation, &liquidity, &collateral)
+            .unwrap();
+        assert_eq!(result.settle_amount, Decimal::one());
+        assert_eq!(result.repay_amount, 1);
+        assert_eq!(result.withdraw_amount, 5);
+
+        assert_eq!(
+            reserve
+                .calculate_liquidation(0, &obligation, &liquidity, &collateral)
+                .map(|_| ()),
+            Err(LendingError::LiquidationTooSmall.into())
+        );
+    }
+
+    #[test]
+    fn calculate_borrow_fees() {
+        let fees = ReserveFees {
+            borrow_fee_wad: 10_000_000_000_000_000, // 1%
+            host_fee_percentage: 20,
+            ..ReserveFees::default()
+        };
+
+        // fee = 1% of 1000, the host takes 20% of it
+        assert_eq!(
+            fees.calculate_borrow_fees(Decimal::from(1_000u64), FeeCalculation::Exclusive),
+            Ok((10, 2))
+        );
+        // fee = 1000 * 0.01 / 1.01
+        assert_eq!(
+            fees.calculate_borrow_fees(Decimal::from(1_000u64), FeeCalculation::Inclusive),
+            Ok((10, 2))
+        );
+        // minimum fee of 1 token each for the owner and host
+        assert_eq!(
+            fees.calculate_borrow_fees(Decimal::from(10u64), FeeCalculation::Exclusive),
+            Ok((2, 1))
+        );
+        assert_eq!(
+            fees.calculate_borrow_fees(Decimal::from(2u64), FeeCalculation::Exclusive),
+            Err(LendingError::BorrowTooSmall.into())
+        );
+
+        let fees = ReserveFees {
+            borrow_fee_wad: 10_000_000_000_000_000,
+            ..ReserveFees::default()
+        };
+        assert_eq!(
+            fees.calculate_borrow_fees(Decimal::from(10u64), FeeCalculation::Exclusive),
+            Ok((1, 0))
+        );
+        assert_eq!(
+            fees.calculate_borrow_fees(Decimal::one(), FeeCalculation::Exclusive),
+            Err(LendingError::BorrowTooSmall.into())
+        );
+        assert_eq!(
+            ReserveFees::default()
+                .calculate_borrow_fees(Decimal::one(), FeeCalculation::Exclusive),
+            Ok((0, 0))
+        );
+    }
+
+    #[test]
+    fn calculate_flash_loan_fees() {
+        let fees = ReserveFees {
+            flash_loan_fee_wad: 3_000_000_000_000_000, // 0.3%
+            host_fee_percentage: 20,
+            ..ReserveFees::default()
+        };
+
+        assert_eq!(
+            fees.calculate_flash_loan_fees(Decimal::from(100_000u64)),
+            Ok((300, 60))
+        );
+        assert_eq!(fees.calculate_flash_loan_fees(Decimal::from(100u64)), Ok((2, 1)));
+        assert_eq!(
+            fees.calculate_flash_loan_fees(Decimal::from(2u64)),
+            Err(LendingError::BorrowTooSmall.into())
+        );
+        assert_eq!(fees.calculate_flash_loan_fees(Decimal::zero()), Ok((0, 0)));
+    }
+
+    #[test]
+    fn accrue_mining_index() {
+        let mut reserve = borrow_rate_reserve(60, 40);
+        reserve.collateral.mint_total_supply = 100;
+        reserve.bonus.total_mining_speed = 100;
+        reserve.bonus.supply_rate = 50;
+        reserve.last_update = LastUpdate::new(0);
+
+        // 40% utilization is below the kink, borrowers get 40% of the mine
+        assert_eq!(reserve.borrow_mining_rate(), Ok(Rate::from_percent(40)));
+        let mut accrued = reserve.clone();
+        accrued.accrue_mining_index(10).unwrap();
+        assert_eq!(accrued.bonus.l_token_mining_index, Decimal::from(6u64));
+        assert_eq!(accrued.bonus.borrow_mining_index, Decimal::from(10u64));
+        assert_eq!(reserve.project_to_slot(10).unwrap().bonus, reserve.bonus);
+
+        // above the kink the borrowers' share stays at 50%
+        reserve.liquidity.available_amount = 10;
+        reserve.liquidity.borrowed_amount_wads = Decimal::from(90u64);
+        assert_eq!(reserve.borrow_mining_rate(), Ok(Rate::from_percent(50)));
+        reserve.accrue_mining_index(9).unwrap();
+        assert_eq!(
+            reserve.bonus.l_token_mining_index,
+            Decimal::from(45u64).try_div(10u64).unwrap()
+        );
+        assert_eq!(reserve.bonus.borrow_mining_index, Decimal::from(5u64));
+
+        // no borrows, suppliers get everything
+        let mut reserve = borrow_rate_reserve(100, 0);
+        reserve.collateral.mint_total_supply = 100;
+        reserve.bonus.total_mining_speed = 100;
+        reserve.bonus.supply_rate = 50;
+        reserve.accrue_mining_index(1).unwrap();
+        assert_eq!(reserve.bonus.l_token_mining_index, Decimal::one());
+        assert_eq!(reserve.bonus.borrow_mining_index, Decimal::zero());
+
+        // the supply rate is a percentage, larger ones never kink
+        reserve.bonus.supply_rate = 100;
+        assert_eq!(reserve.bonus.kink_util_rate(), Rate::one());
+        reserve.bonus.supply_rate = 5000;
+        assert_eq!(reserve.bonus.kink_util_rate(), Rate::one());
+        reserve.liquidity.available_amount = 10;
+        reserve.liquidity.borrowed_amount_wads = Decimal::from(90u64);
+        assert_eq!(reserve.borrow_mining_rate(), Ok(Rate::from_percent(90)));
+        assert_eq!(reserve.accrue_mining_index(2), Ok(()));
+    }
+}
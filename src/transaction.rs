@@ -0,0 +1,235 @@
+//! Transactions refreshing reserves and obligations ahead of an action
+
+use crate::{
+    error::LendingError,
+    instruction::{refresh_obligation, refresh_reserves},
+    state::{obligation::Obligation, reserve::Reserve},
+};
+use solana_program::{instruction::Instruction, msg, program_error::ProgramError, pubkey::Pubkey};
+
+/// Reserve accounts of a `RefreshObligation` instruction: the collateral deposit reserves
+/// followed by the liquidity borrow reserves, each in the obligation's order
+pub fn refresh_obligation_reserve_pubkeys(obligation: &Obligation) -> Vec<Pubkey> {
+    obligation
+        .deposits
+        .iter()
+        .map(|collateral| collateral.deposit_reserve)
+        .chain(obligation.borrows.iter().map(|liquidity| liquidity.borrow_reserve))
+        .collect()
+}
+
+/// Creates the instructions of an obligation action.
+///
+/// A `RefreshReserves` for every reserve of the obligation and every reserve in
+/// `action_reserve_pubkeys` comes first, then a `RefreshObligation`, then `action` itself.
+/// `action_reserve_pubkeys` are the reserves `action` operates on, such as a borrow reserve not
+/// yet in the obligation. `reserves` must contain each of these reserves and each reserve of the
+/// obligation.
+pub fn refresh_and_act(
+    program_id: Pubkey,
+    obligation_pubkey: Pubkey,
+    obligation: &Obligation,
+    reserves: &[(Pubkey, Reserve)],
+    action_reserve_pubkeys: &[Pubkey],
+    action: Instruction,
+) -> Result<Vec<Instruction>, ProgramError> {
+    let obligation_reserve_pubkeys = refresh_obligation_reserve_pubkeys(obligation);
+
+    let mut refreshed_reserves: Vec<(Pubkey, Pubkey)> = vec![];
+    for reserve_pubkey in obligation_reserve_pubkeys
+        .iter()
+        .cloned()
+        .chain(action_reserve_pubkeys.iter().cloned())
+    {
+        if refreshed_reserves.iter().any(|(pubkey, _)| *pubkey == reserve_pubkey) {
+            continue;
+        }
+        let reserve = reserves
+            .iter()
+            .find(|(pubkey, _)| *pubkey == reserve_pubkey)
+            .map(|(_, reserve)| reserve)
+            .ok_or_else(|| {
+                msg!("Reserve {} to refresh is missing", reserve_pubkey);
+                ProgramError::from(LendingError::InvalidAccountInput)
+            })?;
+        refreshed_reserves.push((reserve_pubkey, reserve.liquidity.oracle_pubkey()));
+    }
+
+    let mut instructions = Vec::with_capacity(3);
+    if !refreshed_reserves.is_empty() {
+        let (reserves, oracles) = refreshed_reserves.into_iter().unzip();
+        instructions.push(refresh_reserves(program_id, reserves, oracles));
+    }
+    instructions.push(refresh_obligation(
+        program_id,
+        obligation_pubkey,
+        obligation_reserve_pubkeys,
+    ));
+    instructions.push(action);
+    Ok(instructions)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        instruction::{borrow_obligation_liquidity, LendingInstruction},
+        state::{
+            obligation::{ObligationCollateral, ObligationLiquidity},
+            reserve::ReserveLiquidity,
+        },
+    };
+
+    fn reserve(use_pyth_oracle: bool, is_lp: bool) -> (Pubkey, Reserve) {
+        let reserve = Reserve {
+            liquidity: ReserveLiquidity {
+                is_lp,
+                use_pyth_oracle,
+                params_1: Pubkey::new_unique(),
+                params_2: Pubkey::new_unique(),
+                ..ReserveLiquidity::default()
+            },
+            ..Reserve::default()
+        };
+        (Pubkey::new_unique(), reserve)
+    }
+
+    #[test]
+    fn refresh_then_borrow() {
+        let pyth = reserve(true, false);
+        let larix = reserve(false, false);
+        let lp = reserve(true, true);
+        let new_borrow = reserve(true, false);
+        let unrelated = reserve(false, false);
+        let obligation = Obligation {
+            deposits: vec![
+                ObligationCollateral {
+                    deposit_reserve: lp.0,
+                    ..ObligationCollateral::default()
+                },
+                ObligationCollateral {
+                    deposit_reserve: pyth.0,
+                    ..ObligationCollateral::default()
+                },
+            ],
+            borrows: vec![
+                ObligationLiquidity {
+                    borrow_reserve: larix.0,
+                    ..ObligationLiquidity::default()
+                },
+                ObligationLiquidity {
+                    borrow_reserve: pyth.0,
+                    ..ObligationLiquidity::default()
+                },
+            ],
+            ..Obligation::default()
+        };
+        let reserves = vec![
+            unrelated.clone(),
+            new_borrow.clone(),
+            larix.clone(),
+            pyth.clone(),
+            lp.clone(),
+        ];
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+        let action = borrow_obligation_liquidity(
+            crate::id(),
+            100,
+            keys[0],
+            keys[1],
+            new_borrow.0,
+            keys[2],
+            keys[3],
+            keys[4],
+            keys[5],
+            keys[6],
+            keys[7],
+            keys[7],
+        );
+
+        let instructions = refresh_and_act(
+            crate::id(),
+            keys[3],
+            &obligation,
+            &reserves,
+            &[new_borrow.0],
+            action.clone(),
+        )
+        .unwrap();
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(
+            instructions[0],
+            refresh_reserves(
+                crate::id(),
+                vec![lp.0, pyth.0, larix.0, new_borrow.0],
+                vec![
+                    lp.1.liquidity.params_2,
+                    pyth.1.liquidity.params_1,
+                    larix.1.liquidity.params_2,
+                    new_borrow.1.liquidity.params_1,
+                ],
+            )
+        );
+        assert_eq!(
+            instructions[1],
+            refresh_obligation(crate::id(), keys[3], vec![lp.0, pyth.0, larix.0, pyth.0])
+        );
+        assert_eq!(instructions[2], action);
+    }
+
+    #[test]
+    fn refresh_empty_obligation() {
+        let obligation = Obligation::default();
+        let obligation_pubkey = Pubkey::new_unique();
+        let action = refresh_obligation(crate::id(), obligation_pubkey, vec![]);
+        let instructions =
+            refresh_and_act(crate::id(), obligation_pubkey, &obligation, &[], &[], action).unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(
+            LendingInstruction::unpack(&instructions[0].data),
+            Ok(LendingInstruction::RefreshObligation)
+        );
+    }
+
+    #[test]
+    fn refresh_missing_reserve() {
+        let obligation = Obligation {
+            deposits: vec![ObligationCollateral {
+                deposit_reserve: Pubkey::new_unique(),
+                ..ObligationCollateral::default()
+            }],
+            ..Obligation::default()
+        };
+        let obligation_pubkey = Pubkey::new_unique();
+        let action = refresh_obligation(crate::id(), obligation_pubkey, vec![]);
+        assert_eq!(
+            refresh_and_act(crate::id(), obligation_pubkey, &obligation, &[], &[], action),
+            Err(LendingError::InvalidAccountInput.into())
+        );
+    }
+
+    #[test]
+    fn refresh_missing_action_reserve() {
+        let deposit = reserve(true, false);
+        let obligation = Obligation {
+            deposits: vec![ObligationCollateral {
+                deposit_reserve: deposit.0,
+                ..ObligationCollateral::default()
+            }],
+            ..Obligation::default()
+        };
+        let obligation_pubkey = Pubkey::new_unique();
+        let action = refresh_obligation(crate::id(), obligation_pubkey, vec![]);
+        assert_eq!(
+            refresh_and_act(
+                crate::id(),
+                obligation_pubkey,
+                &obligation,
+                &[deposit],
+                &[Pubkey::new_unique()],
+                action,
+            ),
+            Err(LendingError::InvalidAccountInput.into())
+        );
+    }
+}